@@ -3,9 +3,10 @@ use crate::configuration;
 use crate::constants;
 use crate::json_data;
 use crate::request;
+use crate::time_parser;
 use crate::usage;
+use crate::util;
 
-use chrono::DateTime;
 use http::StatusCode;
 use std::collections::HashMap;
 use std::error::Error;
@@ -17,9 +18,7 @@ pub fn run(
     let mut sticky = false;
     let mut persistent = false;
     let mut notify = true;
-    let mut expire_stamp_str = String::new();
-    let mut ack_type = "";
-    let mut filter = String::new();
+    let mut expiry = None;
     let mut state_map = HashMap::new();
 
     if opt.is_present("help") {
@@ -28,14 +27,7 @@ pub fn run(
         return Ok(());
     };
 
-    let hosts = match opt.value_of("host_object") {
-        Some(v) => v.to_string(),
-        None => String::new(),
-    };
-    let services = match opt.value_of("service_object") {
-        Some(v) => v.to_string(),
-        None => String::new(),
-    };
+    let selector = command::filter::ObjectSelector::from_options(opt);
     let comment = match opt.value_of("comment") {
         Some(v) => v.to_string(),
         None => bail!("Acknowledgement comment is mandatory"),
@@ -84,54 +76,35 @@ pub fn run(
     }
 
     if !expire_str.is_empty() {
-        match DateTime::parse_from_rfc3339(expire_str.as_str()) {
-            Ok(v) => expire_stamp_str = format!("\"expiry\":{},", v.format("%s").to_string()),
-            Err(e) => bail!("Can't parse expiration time as RFC3339 time: {}", e),
+        match time_parser::parse_time(&expire_str) {
+            Ok(v) => expiry = Some(v),
+            Err(e) => bail!("Can't parse expiration time: {}", e),
         };
     };
 
-    if hosts.is_empty() && services.is_empty() {
-        bail!("Provide a host and/or service to add acknowledgement for.");
+    if selector.is_empty() {
+        bail!("Provide a host, service, group and/or filter to add acknowledgement for.");
     }
 
-    if !hosts.is_empty() && services.is_empty() {
-        ack_type = "Host";
-        filter = format!(
-            "match(\\\"{}\\\", host.name) && host.state_type == 1",
-            hosts
-        );
-    }
-
-    if hosts.is_empty() && !services.is_empty() {
-        ack_type = "Service";
-        filter = format!(
-            "match(\\\"{}\\\", service.name) && service.state_type == 1",
-            services
-        );
-    }
-
-    if !hosts.is_empty() && !services.is_empty() {
-        ack_type = "Service";
-        filter = format!("match(\\\"{}\\\", host.name) && match(\\\"{}\\\", service.name) && service.state_type == 1", hosts, services);
-    }
+    let (ack_type, mut filter) = command::filter::build_object_filter(&selector);
+    let obj = ack_type.to_lowercase();
 
+    filter.add(&format!("{}.state_type == 1", obj));
     if !state_map.is_empty() {
-        filter = format!(
-            "{} && ({})",
-            filter,
-            command::filter::build_state_filter(&ack_type.to_lowercase(), &state_map)
-        );
+        filter.add(&command::filter::build_state_filter(&obj, &state_map));
     }
 
-    let payload = format!("{{\"type\":\"{acktype}\",\"filter\":\"{filter}\",\"author\":\"{author}\",\"comment\":\"{comment}\",{expiry}\"sticky\":{sticky},\"notify\":{notify},\"persistent\":{persistent}}}",
-                          acktype=ack_type,
-                          filter=filter,
-                          author=author,
-                          comment=comment,
-                          sticky=sticky,
-                          expiry=expire_stamp_str,
-                          notify=notify,
-                          persistent=persistent);
+    let payload = serde_json::to_string(&json_data::AddAckRequest {
+        object_type: ack_type.to_string(),
+        filter: filter.expression(),
+        filter_vars: filter.vars(),
+        author,
+        comment,
+        expiry,
+        sticky,
+        notify,
+        persistent,
+    })?;
 
     if cfg.debug {
         eprintln!("HTTP method: POST");
@@ -139,6 +112,10 @@ pub fn run(
         eprintln!("Payload: {}", payload);
     }
 
+    if !command::objects::preflight(cfg, constants::ICINGA2_ADD_ACK, ack_type, &filter, &payload)? {
+        return Ok(());
+    }
+
     let req = request::build_client(cfg, "")?
         .post(
             format!(
@@ -177,7 +154,7 @@ pub fn run(
     };
 
     for ar in action_result.results {
-        println!("{}", ar.status);
+        println!("{}{}", util::instance_prefix(cfg), ar.status);
     }
 
     Ok(())
@@ -1,8 +1,10 @@
+use crate::command;
 use crate::configuration;
 use crate::constants;
 use crate::json_data;
 use crate::request;
 use crate::usage;
+use crate::util;
 
 use http::StatusCode;
 use std::error::Error;
@@ -11,23 +13,13 @@ pub fn run(
     cfg: &configuration::Configuration,
     opt: &clap::ArgMatches,
 ) -> Result<(), Box<dyn Error>> {
-    let mut cmt_type = "";
-    let mut filter = String::new();
-
     if opt.is_present("help") {
         usage::version::show();
         usage::add_comment::show();
         return Ok(());
     };
 
-    let hosts = match opt.value_of("host_object") {
-        Some(v) => v.to_string(),
-        None => String::new(),
-    };
-    let services = match opt.value_of("service_object") {
-        Some(v) => v.to_string(),
-        None => String::new(),
-    };
+    let selector = command::filter::ObjectSelector::from_options(opt);
     let comment = match opt.value_of("comment") {
         Some(v) => v.to_string(),
         None => bail!("Comment comment is mandatory"),
@@ -48,34 +40,19 @@ pub fn run(
         }
     };
 
-    if hosts.is_empty() && services.is_empty() {
-        bail!("Provide a host and/or service for comment addition");
-    }
-
-    if !hosts.is_empty() && services.is_empty() {
-        cmt_type = "Host";
-        filter = format!("match(\\\"{}\\\", host.name)", hosts);
+    if selector.is_empty() {
+        bail!("Provide a host, service, group and/or filter for comment addition");
     }
 
-    if hosts.is_empty() && !services.is_empty() {
-        cmt_type = "Service";
-        filter = format!("match(\\\"{}\\\", service.name)", services);
-    }
-
-    if !hosts.is_empty() && !services.is_empty() {
-        cmt_type = "Service";
-        filter = format!(
-            "match(\\\"{}\\\", host.name) && match(\\\"{}\\\", service.name)",
-            hosts, services
-        );
-    }
+    let (cmt_type, filter) = command::filter::build_object_filter(&selector);
 
-    let payload = format!("{{\"type\":\"{cmttype}\",\"filter\":\"{filter}\",\"author\":\"{author}\",\"comment\":\"{comment}\"}}",
-                          author=author,
-                          comment=comment,
-                          cmttype=cmt_type,
-                          filter=filter,
-                          );
+    let payload = serde_json::to_string(&json_data::AddCommentRequest {
+        object_type: cmt_type.to_string(),
+        filter: filter.expression(),
+        filter_vars: filter.vars(),
+        author,
+        comment,
+    })?;
 
     if cfg.debug {
         eprintln!("HTTP method: POST");
@@ -83,6 +60,16 @@ pub fn run(
         eprintln!("Payload: {}", payload);
     }
 
+    if !command::objects::preflight(
+        cfg,
+        constants::ICINGA2_ADD_COMMENT,
+        cmt_type,
+        &filter,
+        &payload,
+    )? {
+        return Ok(());
+    }
+
     let req = request::build_client(cfg, "")?
         .post(
             format!(
@@ -123,10 +110,10 @@ pub fn run(
     for ar in action_result.results {
         match ar.name {
             Some(v) => {
-                println!("{}: {}", v, ar.status);
+                println!("{}{}: {}", util::instance_prefix(cfg), v, ar.status);
             }
             None => {
-                println!("{}", ar.status);
+                println!("{}{}", util::instance_prefix(cfg), ar.status);
             }
         };
     }
@@ -1,26 +1,23 @@
+use crate::command;
 use crate::configuration;
 use crate::constants;
 use crate::json_data;
 use crate::request;
+use crate::time_parser;
 use crate::usage;
+use crate::util;
 
-use chrono::DateTime;
 use http::StatusCode;
 use std::error::Error;
-use std::str::FromStr;
 
 pub fn run(
     cfg: &configuration::Configuration,
     opt: &clap::ArgMatches,
 ) -> Result<(), Box<dyn Error>> {
-    let mut start_stamp_str = String::new();
-    let mut end_stamp_str = String::new();
+    let mut start_time = None;
+    let mut end_time = None;
     let mut all_services = false;
     let mut fixed = false;
-    let mut filter = String::new();
-    let mut dwn_type = "";
-    let mut duration_str = String::new();
-    let mut trigger_str = String::new();
 
     if opt.is_present("help") {
         usage::version::show();
@@ -28,14 +25,7 @@ pub fn run(
         return Ok(());
     };
 
-    let hosts = match opt.value_of("host_object") {
-        Some(v) => v.to_string(),
-        None => String::new(),
-    };
-    let services = match opt.value_of("service_object") {
-        Some(v) => v.to_string(),
-        None => String::new(),
-    };
+    let selector = command::filter::ObjectSelector::from_options(opt);
     let comment = match opt.value_of("comment") {
         Some(v) => v.to_string(),
         None => bail!("Downtime comment is mandatory"),
@@ -66,7 +56,7 @@ pub fn run(
     };
 
     let duration = match opt.value_of("duration") {
-        Some(v) => match i32::from_str(v) {
+        Some(v) => match time_parser::parse_duration(v) {
             Ok(vv) => {
                 if vv > 0 {
                     vv
@@ -75,7 +65,7 @@ pub fn run(
                 }
             }
             Err(e) => {
-                bail!("Can't convert duration into a number: {}", e);
+                bail!("Can't parse duration: {}", e);
             }
         },
         None => -1,
@@ -94,28 +84,18 @@ pub fn run(
         None => String::new(),
     };
 
-    let child_opts = match opt.value_of("child_opts") {
-        Some(v) => match v.to_lowercase().as_str() {
-            "downtimenochildren" => "DowntimeNoChildren",
-            "downtimetriggeredchildren" => "DowntimeTriggeredChildren",
-            "downtimenontriggeredchildren" => "DowntimeNonTriggeredChildren",
-            _ => {
-                bail!("Invalid child option {}", v);
-            }
-        },
-        None => "DowntimeNoChildren",
-    };
+    let child_opts = parse_child_options(opt.value_of("child_opts"))?;
 
     if !start_str.is_empty() {
-        match DateTime::parse_from_rfc3339(start_str.as_str()) {
-            Ok(v) => start_stamp_str = v.format("%s").to_string(),
-            Err(e) => bail!("Can't parse start time as RFC3339 time: {}", e),
+        match time_parser::parse_time(&start_str) {
+            Ok(v) => start_time = Some(v),
+            Err(e) => bail!("Can't parse start time: {}", e),
         };
     };
     if !end_str.is_empty() {
-        match DateTime::parse_from_rfc3339(end_str.as_str()) {
-            Ok(v) => end_stamp_str = v.format("%s").to_string(),
-            Err(e) => bail!("Can't parse end time as RFC3339 time: {}", e),
+        match time_parser::parse_time(&end_str) {
+            Ok(v) => end_time = Some(v),
+            Err(e) => bail!("Can't parse end time: {}", e),
         };
     };
 
@@ -123,60 +103,94 @@ pub fn run(
         bail!("Flexible downtime must have a duration");
     }
 
-    if all_services && !services.is_empty() {
+    let (start_time, end_time) = downtime_window(
+        start_time,
+        end_time,
+        duration,
+        chrono::Local::now().timestamp(),
+    )?;
+
+    if all_services && selector.object_type() == "Service" {
         bail!("Adding downtime for all services and adding downtime for specific services are mutually exclusive");
     }
 
-    if hosts.is_empty() && services.is_empty() {
-        bail!("Provide a host and/or service for downtime addition");
+    if selector.is_empty() {
+        bail!("Provide a host, service, group and/or filter for downtime addition");
     }
 
-    if !hosts.is_empty() && services.is_empty() {
-        dwn_type = "Host";
-        filter = format!("match(\\\"{}\\\", host.name)", hosts);
-    }
+    let (dwn_type, filter) = command::filter::build_object_filter(&selector);
 
-    if hosts.is_empty() && !services.is_empty() {
-        dwn_type = "Service";
-        filter = format!("match(\\\"{}\\\", service.name)", services);
-    }
+    let payload = serde_json::to_string(&json_data::AddDowntimeRequest {
+        object_type: dwn_type.to_string(),
+        filter: filter.expression(),
+        filter_vars: filter.vars(),
+        author,
+        comment,
+        start_time,
+        end_time,
+        fixed,
+        duration: if duration > 0 { Some(duration) } else { None },
+        all_services,
+        trigger_name: trigger,
+        child_options: child_opts.to_string(),
+    })?;
 
-    if !hosts.is_empty() && !services.is_empty() {
-        dwn_type = "Service";
-        filter = format!(
-            "match(\\\"{}\\\", host.name) && match(\\\"{}\\\", service.name)",
-            hosts, services
-        );
+    if cfg.debug {
+        eprintln!("HTTP method: POST");
+        eprintln!("URL: {}{}", cfg.url, constants::ICINGA2_ADD_DOWNTIME);
+        eprintln!("Payload: {}", payload);
     }
 
-    if duration > 0 {
-        duration_str = format!(",\"duration\":\"{}\"", duration);
+    if !command::objects::preflight(
+        cfg,
+        constants::ICINGA2_ADD_DOWNTIME,
+        dwn_type,
+        &filter,
+        &payload,
+    )? {
+        return Ok(());
     }
 
-    if !trigger.is_empty() {
-        trigger_str = format!(",\"trigger_name\":\"{}\"", trigger);
+    schedule(cfg, payload)
+}
+
+pub fn parse_child_options(opt: Option<&str>) -> Result<&'static str, Box<dyn Error>> {
+    match opt {
+        Some(v) => match v.to_lowercase().as_str() {
+            "no" | "downtimenochildren" => Ok("DowntimeNoChildren"),
+            "trigger" | "downtimetriggeredchildren" => Ok("DowntimeTriggeredChildren"),
+            "no-trigger" | "downtimenontriggeredchildren" => Ok("DowntimeNonTriggeredChildren"),
+            _ => {
+                bail!("Invalid child option {}", v);
+            }
+        },
+        None => Ok("DowntimeNoChildren"),
     }
+}
 
-    let payload = format!("{{\"type\":\"{dwntype}\",\"filter\":\"{filter}\",\"author\":\"{author}\",\"comment\":\"{comment}\",\"start_time\":\"{start_time}\",\"end_time\":\"{end_time}\",\"fixed\":{fixed}{duration},\"all_services\":{all_svc}{trigger},\"child_options\":\"{child_options}\"}}",
-                          all_svc=all_services,
-                          author=author,
-                          child_options=child_opts,
-                          comment=comment,
-                          duration=duration_str,
-                          dwntype=dwn_type,
-                          end_time=end_stamp_str,
-                          filter=filter,
-                          fixed=fixed,
-                          start_time=start_stamp_str,
-                          trigger=trigger_str,
-                          );
+// Send the schedule-downtime action and print the result
+pub fn schedule(cfg: &configuration::Configuration, payload: String) -> Result<(), Box<dyn Error>> {
+    let action_result = send(cfg, payload)?;
 
-    if cfg.debug {
-        eprintln!("HTTP method: POST");
-        eprintln!("URL: {}{}", cfg.url, constants::ICINGA2_ADD_DOWNTIME);
-        eprintln!("Payload: {}", payload);
+    for ar in action_result.results {
+        match ar.name {
+            Some(v) => {
+                println!("{}{}: {}", util::instance_prefix(cfg), v, ar.status);
+            }
+            None => {
+                println!("{}{}", util::instance_prefix(cfg), ar.status);
+            }
+        };
     }
 
+    Ok(())
+}
+
+// Send the schedule-downtime action and return the reply
+pub fn send(
+    cfg: &configuration::Configuration,
+    payload: String,
+) -> Result<json_data::Icinga2ActionReply, Box<dyn Error>> {
     let req = request::build_client(cfg, "")?
         .post(
             format!(
@@ -207,23 +221,99 @@ pub fn run(
 
     let raw = req.text()?;
 
-    let action_result: json_data::Icinga2ActionReply = match serde_json::from_str(&raw.as_str()) {
-        Ok(v) => v,
-        Err(e) => {
-            return Err(Box::new(e));
-        }
-    };
+    match serde_json::from_str(&raw.as_str()) {
+        Ok(v) => Ok(v),
+        Err(e) => Err(Box::new(e)),
+    }
+}
 
-    for ar in action_result.results {
-        match ar.name {
-            Some(v) => {
-                println!("{}: {}", v, ar.status);
+// Complete the downtime window: the start defaults to now and the end to start + duration.
+// Returns the start and end time of the downtime.
+pub fn downtime_window(
+    start: Option<i64>,
+    end: Option<i64>,
+    duration: i64,
+    now: i64,
+) -> Result<(i64, i64), Box<dyn Error>> {
+    let start = start.unwrap_or(now);
+    let end = match end {
+        Some(v) => v,
+        None => {
+            if duration <= 0 {
+                bail!("Provide the end time or the duration of the downtime");
             }
-            None => {
-                println!("{}", ar.status);
+            match start.checked_add(duration) {
+                Some(v) => v,
+                None => bail!("Duration of the downtime is too large"),
             }
-        };
+        }
+    };
+
+    if end <= start {
+        bail!("End time of the downtime must be after the start time");
     }
 
-    Ok(())
+    if end <= now {
+        bail!("Downtime would end in the past");
+    }
+
+    Ok((start, end))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn child_options() {
+        assert_eq!(parse_child_options(None).unwrap(), "DowntimeNoChildren");
+        assert_eq!(
+            parse_child_options(Some("no")).unwrap(),
+            "DowntimeNoChildren"
+        );
+        assert_eq!(
+            parse_child_options(Some("trigger")).unwrap(),
+            "DowntimeTriggeredChildren"
+        );
+        assert_eq!(
+            parse_child_options(Some("no-trigger")).unwrap(),
+            "DowntimeNonTriggeredChildren"
+        );
+        assert_eq!(
+            parse_child_options(Some("DowntimeTriggeredChildren")).unwrap(),
+            "DowntimeTriggeredChildren"
+        );
+        assert!(parse_child_options(Some("yes")).is_err());
+    }
+
+    #[test]
+    fn window_defaults() {
+        assert_eq!(
+            downtime_window(None, None, 3600, 1000).unwrap(),
+            (1000, 4600)
+        );
+        assert_eq!(
+            downtime_window(Some(2000), None, 60, 1000).unwrap(),
+            (2000, 2060)
+        );
+        assert_eq!(
+            downtime_window(None, Some(5000), -1, 1000).unwrap(),
+            (1000, 5000)
+        );
+        // a downtime that already started but ends in the future is fine
+        assert_eq!(
+            downtime_window(Some(500), Some(1500), -1, 1000).unwrap(),
+            (500, 1500)
+        );
+    }
+
+    #[test]
+    fn invalid_windows() {
+        assert!(downtime_window(None, None, -1, 1000).is_err());
+        assert!(downtime_window(Some(2000), Some(2000), -1, 1000).is_err());
+        assert!(downtime_window(Some(3000), Some(2000), -1, 1000).is_err());
+        assert!(downtime_window(Some(100), Some(900), -1, 1000).is_err());
+        assert!(downtime_window(Some(100), None, 600, 1000).is_err());
+        assert!(downtime_window(Some(100), None, i64::MAX, 1000).is_err());
+    }
 }
@@ -0,0 +1,68 @@
+use crate::configuration;
+use crate::constants;
+use crate::output;
+use crate::usage;
+use crate::util;
+
+use std::error::Error;
+
+pub fn run(
+    config_file: &str,
+    overrides: &[(String, String)],
+    output_format: u8,
+    opt: &clap::ArgMatches,
+) -> Result<(), Box<dyn Error>> {
+    if opt.is_present("help") {
+        usage::version::show();
+        usage::config::show();
+        return Ok(());
+    }
+
+    match opt.subcommand() {
+        ("list", Some(_)) => list(config_file, overrides, output_format),
+        _ => {
+            bail!("No configuration command provided or unknown configuration command");
+        }
+    }
+}
+
+fn list(
+    config_file: &str,
+    overrides: &[(String, String)],
+    output_format: u8,
+) -> Result<(), Box<dyn Error>> {
+    let (instances, default) = configuration::get_instances(config_file, overrides)?;
+    let mut records = Vec::new();
+
+    for (name, cfg) in instances {
+        records.push(output::InstanceRecord {
+            default: name == default,
+            name,
+            url: cfg.url,
+            auth: match cfg.auth {
+                constants::AUTH_USER => "user",
+                constants::AUTH_CERT => "cert",
+                _ => "???",
+            }
+            .to_string(),
+        });
+    }
+
+    if output_format != constants::OUTPUT_TEXT {
+        return output::print_records(&records, output_format);
+    }
+
+    let mut rows = Vec::new();
+    for r in records {
+        rows.push(vec![
+            r.name,
+            if r.default { "*" } else { "" }.to_string(),
+            r.auth,
+            r.url,
+        ]);
+    }
+
+    util::print_table(&["NAME", "DEFAULT", "AUTH", "URL"], &rows);
+
+    Ok(())
+}
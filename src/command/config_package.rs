@@ -0,0 +1,286 @@
+use crate::command;
+use crate::configuration;
+use crate::constants;
+use crate::json_data;
+use crate::request;
+
+use http::StatusCode;
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::thread;
+use std::time::{Duration, Instant};
+
+// Configuration objects created by icinga2ctl are stored in the config package icinga2ctl.
+// Uploading a stage replaces all files of the package, so the files of the active stage are
+// kept and only the given files are added or replaced.
+// The upload only creates the stage, Icinga2 validates the configuration in the background and
+// only activates it if the validation succeeds.
+// Returns the name of the new stage and the status reported by Icinga2.
+pub fn deploy(
+    cfg: &configuration::Configuration,
+    files: BTreeMap<String, String>,
+) -> Result<(String, String), Box<dyn Error>> {
+    let package = constants::NAME;
+
+    let packages: json_data::Icinga2ConfigPackages = command::objects::get_objects(
+        cfg,
+        constants::ICINGA2_CONFIG_PACKAGES,
+        "",
+        "{}".to_string(),
+    )?;
+
+    let mut stage_files = BTreeMap::new();
+
+    match packages.results.into_iter().find(|p| p.name == package) {
+        Some(p) => {
+            if !p.active_stage.is_empty() {
+                stage_files = get_stage_files(cfg, package, &p.active_stage)?;
+            }
+        }
+        None => {
+            post(
+                cfg,
+                &format!("{}/{}", constants::ICINGA2_CONFIG_PACKAGES, package),
+                String::new(),
+            )?;
+        }
+    };
+
+    stage_files.extend(files);
+
+    let payload = serde_json::to_string(&json_data::ConfigStageRequest { files: stage_files })?;
+    let raw = post(
+        cfg,
+        &format!("{}/{}", constants::ICINGA2_CONFIG_STAGES, package),
+        payload,
+    )?;
+
+    let reply: json_data::Icinga2ConfigReply = serde_json::from_str(&raw)?;
+    match reply.results.into_iter().next() {
+        Some(r) if r.code.round() as i64 == 200 => {
+            let stage = r.stage.unwrap_or_default();
+            wait_for_validation(cfg, package, &stage)?;
+            Ok((stage, r.status))
+        }
+        Some(r) => bail!(
+            "Upload of package {} failed with code {}: {}",
+            package,
+            r.code,
+            r.status
+        ),
+        None => bail!("No result received for the upload of package {}", package),
+    }
+}
+
+// Icinga2 writes the exit code of the validation to the file status of the stage and the output
+// of the validation to startup.log. While Icinga2 reloads, the API may be unreachable.
+fn wait_for_validation(
+    cfg: &configuration::Configuration,
+    package: &str,
+    stage: &str,
+) -> Result<(), Box<dyn Error>> {
+    let timeout = Duration::from_secs(constants::ICINGA2_CONFIG_VALIDATION_TIMEOUT);
+    let start = Instant::now();
+    let mut last_error = String::new();
+
+    while start.elapsed() < timeout {
+        match get_file(cfg, package, stage, "status") {
+            Ok(Some(v)) if v.trim() == "0" => return Ok(()),
+            Ok(Some(v)) => {
+                let log = get_file(cfg, package, stage, "startup.log")?.unwrap_or_default();
+                bail!(
+                    "Validation of stage {} of package {} failed with exit code {}, the stage was not activated:\n{}",
+                    stage,
+                    package,
+                    v.trim(),
+                    log.trim_end()
+                );
+            }
+            Ok(None) => {}
+            Err(e) => last_error = e.to_string(),
+        };
+
+        thread::sleep(Duration::from_secs(1));
+    }
+
+    if last_error.is_empty() {
+        bail!(
+            "Stage {} of package {} was created, but the validation didn't finish within {} seconds",
+            stage,
+            package,
+            constants::ICINGA2_CONFIG_VALIDATION_TIMEOUT
+        );
+    }
+    bail!(
+        "Stage {} of package {} was created, but the result of the validation can't be read: {}",
+        stage,
+        package,
+        last_error
+    );
+}
+
+// Content of a file of a stage, None if the file doesn't exist (yet)
+fn get_file(
+    cfg: &configuration::Configuration,
+    package: &str,
+    stage: &str,
+    name: &str,
+) -> Result<Option<String>, Box<dyn Error>> {
+    let path = format!(
+        "{}/{}/{}/{}",
+        constants::ICINGA2_CONFIG_FILES,
+        package,
+        stage,
+        name
+    );
+
+    if cfg.debug {
+        eprintln!("HTTP method: GET");
+        eprintln!("URL: {}{}", cfg.url, path);
+    }
+
+    let req = request::build_client(cfg, "GET")?
+        .post(format!("{}{}", cfg.url, path).as_str())
+        .send()?;
+
+    if req.status() == StatusCode::NOT_FOUND {
+        return Ok(None);
+    }
+
+    if req.status() != StatusCode::OK {
+        let reason = req.status().canonical_reason().unwrap_or("None");
+        bail!(
+            "Can't read file {} of stage {}, got \"{}\" instead of \"200 OK\"",
+            name,
+            stage,
+            reason
+        );
+    }
+
+    Ok(Some(req.text()?))
+}
+
+// Configuration files of a stage. Only the files below conf.d/ and zones.d/ are uploaded,
+// all other files of a stage are created by Icinga2.
+fn get_stage_files(
+    cfg: &configuration::Configuration,
+    package: &str,
+    stage: &str,
+) -> Result<BTreeMap<String, String>, Box<dyn Error>> {
+    let list: json_data::Icinga2ConfigStageFiles = command::objects::get_objects(
+        cfg,
+        &format!("{}/{}/{}", constants::ICINGA2_CONFIG_STAGES, package, stage),
+        "",
+        "{}".to_string(),
+    )?;
+
+    let mut files = BTreeMap::new();
+    for f in list.results {
+        if f.file_type != "file"
+            || !(f.name.starts_with("conf.d/") || f.name.starts_with("zones.d/"))
+        {
+            continue;
+        }
+
+        match get_file(cfg, package, stage, &f.name)? {
+            Some(v) => {
+                files.insert(f.name, v);
+            }
+            None => bail!("File {} of stage {} doesn't exist", f.name, stage),
+        };
+    }
+
+    Ok(files)
+}
+
+fn post(
+    cfg: &configuration::Configuration,
+    path: &str,
+    payload: String,
+) -> Result<String, Box<dyn Error>> {
+    if cfg.debug {
+        eprintln!("HTTP method: POST");
+        eprintln!("URL: {}{}", cfg.url, path);
+        eprintln!("Payload: {}", payload);
+    }
+
+    let req = request::build_client(cfg, "")?
+        .post(format!("{}{}", cfg.url, path).as_str())
+        .body(payload)
+        .send()?;
+
+    let status = req.status();
+    let raw = req.text()?;
+
+    // Errors of the config API contain the reason in the body, e.g. for invalid configuration
+    if status != StatusCode::OK {
+        bail!(
+            "Invalid status code received, exepected \"200 OK\", got \"{}\" instead: {}",
+            status.canonical_reason().unwrap_or("None"),
+            raw
+        );
+    }
+
+    Ok(raw)
+}
+
+// Quote a string for the Icinga2 configuration language
+pub fn dsl_string(s: &str) -> String {
+    let mut result = String::with_capacity(s.len() + 2);
+
+    result.push('"');
+    for c in s.chars() {
+        match c {
+            '\\' => result.push_str("\\\\"),
+            '"' => result.push_str("\\\""),
+            '\n' => result.push_str("\\n"),
+            '\r' => result.push_str("\\r"),
+            '\t' => result.push_str("\\t"),
+            c if c.is_ascii_control() => result.push_str(&format!("\\{:03o}", c as u32)),
+            c => result.push(c),
+        }
+    }
+    result.push('"');
+
+    result
+}
+
+// Name of the configuration file for an object in the config package
+pub fn config_file_name(name: &str) -> String {
+    let base: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+
+    format!("conf.d/{}.conf", base)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quote_strings() {
+        assert_eq!(dsl_string("patch window"), "\"patch window\"");
+        assert_eq!(
+            dsl_string("a \"b\" \\ c\nd\te\u{1}"),
+            "\"a \\\"b\\\" \\\\ c\\nd\\te\\001\""
+        );
+        assert_eq!(
+            dsl_string("\" } object Host \"x"),
+            "\"\\\" } object Host \\\"x\""
+        );
+    }
+
+    #[test]
+    fn file_names() {
+        assert_eq!(config_file_name("patch-day_1"), "conf.d/patch-day_1.conf");
+        assert_eq!(config_file_name("../x y/z"), "conf.d/.._x_y_z.conf");
+    }
+}
@@ -4,6 +4,7 @@ use crate::constants;
 use crate::json_data;
 use crate::request;
 use crate::usage;
+use crate::util;
 
 use http::StatusCode;
 use std::collections::HashMap;
@@ -13,8 +14,6 @@ pub fn run(
     cfg: &configuration::Configuration,
     opt: &clap::ArgMatches,
 ) -> Result<(), Box<dyn Error>> {
-    let mut ack_type = "";
-    let mut filter = String::new();
     let mut state_map = HashMap::new();
 
     if opt.is_present("help") {
@@ -23,14 +22,7 @@ pub fn run(
         return Ok(());
     };
 
-    let hosts = match opt.value_of("host_object") {
-        Some(v) => v.to_string(),
-        None => String::new(),
-    };
-    let services = match opt.value_of("service_object") {
-        Some(v) => v.to_string(),
-        None => String::new(),
-    };
+    let selector = command::filter::ObjectSelector::from_options(opt);
 
     if opt.is_present("warning") {
         state_map.insert("==1".to_string(), String::new());
@@ -57,45 +49,24 @@ pub fn run(
         }
     };
 
-    if hosts.is_empty() && services.is_empty() {
-        bail!("Provide a host and/or service for acknowledgement deletion");
+    if selector.is_empty() {
+        bail!("Provide a host, service, group and/or filter for acknowledgement deletion");
     }
 
-    if !hosts.is_empty() && services.is_empty() {
-        ack_type = "Host";
-        filter = format!(
-            "match(\\\"{}\\\", host.name) && host.state_type == 1",
-            hosts
-        );
-    }
-
-    if hosts.is_empty() && !services.is_empty() {
-        ack_type = "Service";
-        filter = format!(
-            "match(\\\"{}\\\", service.name) && service.state_type == 1",
-            services
-        );
-    }
-
-    if !hosts.is_empty() && !services.is_empty() {
-        ack_type = "Service";
-        filter = format!("match(\\\"{}\\\", host.name) && match(\\\"{}\\\", service.name) && service.state_type == 1", hosts, services);
-    }
+    let (ack_type, mut filter) = command::filter::build_object_filter(&selector);
+    let obj = ack_type.to_lowercase();
 
+    filter.add(&format!("{}.state_type == 1", obj));
     if !state_map.is_empty() {
-        filter = format!(
-            "{} && ({})",
-            filter,
-            command::filter::build_state_filter(&ack_type.to_lowercase(), &state_map)
-        );
+        filter.add(&command::filter::build_state_filter(&obj, &state_map));
     }
 
-    let payload = format!(
-        "{{\"type\":\"{acktype}\",\"filter\":\"{filter}\",\"author\":\"{author}\"}}",
-        acktype = ack_type,
-        filter = filter,
-        author = author
-    );
+    let payload = serde_json::to_string(&json_data::DelAckRequest {
+        object_type: ack_type.to_string(),
+        filter: filter.expression(),
+        filter_vars: filter.vars(),
+        author,
+    })?;
 
     if cfg.debug {
         eprintln!("HTTP method: POST");
@@ -103,6 +74,10 @@ pub fn run(
         eprintln!("Payload: {}", payload);
     }
 
+    if !command::objects::preflight(cfg, constants::ICINGA2_DEL_ACK, ack_type, &filter, &payload)? {
+        return Ok(());
+    }
+
     let req = request::build_client(cfg, "")?
         .post(
             format!(
@@ -141,7 +116,7 @@ pub fn run(
     };
 
     for ar in action_result.results {
-        println!("{}", ar.status);
+        println!("{}{}", util::instance_prefix(cfg), ar.status);
     }
 
     Ok(())
@@ -1,8 +1,10 @@
+use crate::command;
 use crate::configuration;
 use crate::constants;
 use crate::json_data;
 use crate::request;
 use crate::usage;
+use crate::util;
 
 use http::StatusCode;
 use std::error::Error;
@@ -11,25 +13,13 @@ pub fn run(
     cfg: &configuration::Configuration,
     opt: &clap::ArgMatches,
 ) -> Result<(), Box<dyn Error>> {
-    let mut flt_type = "";
-    let mut filter = String::new();
-    let mut author_str = String::new();
-    let mut filter_str = String::new();
-
     if opt.is_present("help") {
         usage::version::show();
         usage::del_comment::show();
         return Ok(());
     };
 
-    let hosts = match opt.value_of("host_object") {
-        Some(v) => v.to_string(),
-        None => String::new(),
-    };
-    let services = match opt.value_of("service_object") {
-        Some(v) => v.to_string(),
-        None => String::new(),
-    };
+    let selector = command::filter::ObjectSelector::from_options(opt);
 
     let author = match opt.value_of("author") {
         Some(v) => v.to_string(),
@@ -41,46 +31,28 @@ pub fn run(
         None => String::new(),
     };
 
-    if hosts.is_empty() && services.is_empty() && comment.is_empty() {
-        bail!("Neither comment name nor host/service filter provided");
-    }
-
-    if !(comment.is_empty() || hosts.is_empty() || services.is_empty()) {
-        bail!("Provide either a comment name or a host/service filter, but not both");
+    if selector.is_empty() && comment.is_empty() {
+        bail!("Neither comment name nor host/service/group/filter provided");
     }
 
-    if !hosts.is_empty() && services.is_empty() {
-        flt_type = "Host";
-        filter = format!("match(\\\"{}\\\", host.name)", hosts);
+    if !comment.is_empty() && !selector.is_empty() {
+        bail!("Provide either a comment name or a host/service/group/filter, but not both");
     }
 
-    if hosts.is_empty() && !services.is_empty() {
-        flt_type = "Service";
-        filter = format!("match(\\\"{}\\\", service.name)", services);
-    }
-
-    if !hosts.is_empty() && !services.is_empty() {
-        flt_type = "Service";
-        filter = format!(
-            "match(\\\"{}\\\", host.name) && match(\\\"{}\\\", service.name)",
-            hosts, services
-        );
-    }
-
-    if !author.is_empty() {
-        author_str = format!(",\"author\":\"{}\"", author);
-    }
+    let (mut flt_type, filter) = command::filter::build_object_filter(&selector);
 
-    if !filter.is_empty() {
-        filter_str = format!(",\"filter\":\"{}\",\"type\":\"{}\"", filter, flt_type);
+    // Removal by name doesn't use a filter, an object type without a filter selects all objects
+    if selector.is_empty() {
+        flt_type = "";
     }
 
-    let payload = format!(
-        "{{\"comment\":\"{comment}\"{author}{filter}}}",
-        comment = comment,
-        author = author_str,
-        filter = filter_str,
-    );
+    let payload = serde_json::to_string(&json_data::DelCommentRequest {
+        comment: comment.clone(),
+        author,
+        object_type: flt_type.to_string(),
+        filter: filter.expression(),
+        filter_vars: filter.vars(),
+    })?;
 
     if cfg.debug {
         eprintln!("HTTP method: POST");
@@ -88,6 +60,16 @@ pub fn run(
         eprintln!("Payload: {}", payload);
     }
 
+    if !command::objects::preflight(
+        cfg,
+        constants::ICINGA2_DEL_COMMENT,
+        flt_type,
+        &filter,
+        &payload,
+    )? {
+        return Ok(());
+    }
+
     let req = request::build_client(cfg, "")?
         .post(
             format!(
@@ -102,7 +84,11 @@ pub fn run(
 
     match req.status() {
         StatusCode::NOT_FOUND => {
-            println!("Comment {} does not exist", comment);
+            println!(
+                "{}Comment {} does not exist",
+                util::instance_prefix(cfg),
+                comment
+            );
             return Ok(());
         }
         StatusCode::OK => {}
@@ -128,7 +114,7 @@ pub fn run(
     };
 
     for ar in action_result.results {
-        println!("{}", ar.status);
+        println!("{}{}", util::instance_prefix(cfg), ar.status);
     }
 
     Ok(())
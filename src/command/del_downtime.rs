@@ -1,8 +1,11 @@
+use crate::command;
 use crate::configuration;
 use crate::constants;
 use crate::json_data;
 use crate::request;
+use crate::time_parser;
 use crate::usage;
+use crate::util;
 
 use http::StatusCode;
 use std::error::Error;
@@ -11,25 +14,13 @@ pub fn run(
     cfg: &configuration::Configuration,
     opt: &clap::ArgMatches,
 ) -> Result<(), Box<dyn Error>> {
-    let mut flt_type = "";
-    let mut filter = String::new();
-    let mut author_str = String::new();
-    let mut filter_str = String::new();
-
     if opt.is_present("help") {
         usage::version::show();
         usage::del_downtime::show();
         return Ok(());
     };
 
-    let hosts = match opt.value_of("host_object") {
-        Some(v) => v.to_string(),
-        None => String::new(),
-    };
-    let services = match opt.value_of("service_object") {
-        Some(v) => v.to_string(),
-        None => String::new(),
-    };
+    let selector = command::filter::ObjectSelector::from_options(opt);
 
     let author = match opt.value_of("author") {
         Some(v) => v.to_string(),
@@ -41,46 +32,47 @@ pub fn run(
         None => String::new(),
     };
 
-    if hosts.is_empty() && services.is_empty() && downtime.is_empty() {
-        bail!("Neither downtime name nor host/service filter provided");
-    }
-
-    if !(downtime.is_empty() || hosts.is_empty() || services.is_empty()) {
-        bail!("Provide either a downtime name or a host/service filter, but not both");
-    }
+    let by_attributes = [
+        "created_by",
+        "mine",
+        "comment",
+        "expires_before",
+        "expires_after",
+        "created_before",
+        "created_after",
+    ]
+    .iter()
+    .any(|o| opt.is_present(o));
 
-    if !hosts.is_empty() && services.is_empty() {
-        flt_type = "Host";
-        filter = format!("match(\\\"{}\\\", host.name)", hosts);
+    if by_attributes {
+        if !downtime.is_empty() {
+            bail!("Provide either a downtime name or downtime attributes, but not both");
+        }
+        return remove_by_attributes(cfg, opt, &selector, author);
     }
 
-    if hosts.is_empty() && !services.is_empty() {
-        flt_type = "Service";
-        filter = format!("match(\\\"{}\\\", service.name)", services);
+    if selector.is_empty() && downtime.is_empty() {
+        bail!("Neither downtime name nor host/service/group/filter provided");
     }
 
-    if !hosts.is_empty() && !services.is_empty() {
-        flt_type = "Service";
-        filter = format!(
-            "match(\\\"{}\\\", host.name) && match(\\\"{}\\\", service.name)",
-            hosts, services
-        );
+    if !downtime.is_empty() && !selector.is_empty() {
+        bail!("Provide either a downtime name or a host/service/group/filter, but not both");
     }
 
-    if !author.is_empty() {
-        author_str = format!(",\"author\":\"{}\"", author);
-    }
+    let (mut flt_type, filter) = command::filter::build_object_filter(&selector);
 
-    if !filter.is_empty() {
-        filter_str = format!(",\"filter\":\"{}\",\"type\":\"{}\"", filter, flt_type);
+    // Removal by name doesn't use a filter, an object type without a filter selects all objects
+    if selector.is_empty() {
+        flt_type = "";
     }
 
-    let payload = format!(
-        "{{\"downtime\":\"{downtime}\"{author}{filter}}}",
-        downtime = downtime,
-        author = author_str,
-        filter = filter_str,
-    );
+    let payload = serde_json::to_string(&json_data::DelDowntimeRequest {
+        downtime: downtime.clone(),
+        author,
+        object_type: flt_type.to_string(),
+        filter: filter.expression(),
+        filter_vars: filter.vars(),
+    })?;
 
     if cfg.debug {
         eprintln!("HTTP method: POST");
@@ -88,6 +80,16 @@ pub fn run(
         eprintln!("Payload: {}", payload);
     }
 
+    if !command::objects::preflight(
+        cfg,
+        constants::ICINGA2_DEL_DOWNTIME,
+        flt_type,
+        &filter,
+        &payload,
+    )? {
+        return Ok(());
+    }
+
     let req = request::build_client(cfg, "")?
         .post(
             format!(
@@ -102,7 +104,11 @@ pub fn run(
 
     match req.status() {
         StatusCode::NOT_FOUND => {
-            println!("Downtime {} does not exist", downtime);
+            println!(
+                "{}Downtime {} does not exist",
+                util::instance_prefix(cfg),
+                downtime
+            );
             return Ok(());
         }
         StatusCode::OK => {}
@@ -128,8 +134,284 @@ pub fn run(
     };
 
     for ar in action_result.results {
-        println!("{}", ar.status);
+        println!("{}{}", util::instance_prefix(cfg), ar.status);
     }
 
     Ok(())
 }
+
+// Options to select downtimes by time, their names and the filter expressions
+const TIME_OPTIONS: [(&str, &str, &str); 4] = [
+    ("expires_before", "expires-before", "downtime.end_time <"),
+    ("expires_after", "expires-after", "downtime.end_time >"),
+    ("created_before", "created-before", "downtime.entry_time <"),
+    ("created_after", "created-after", "downtime.entry_time >="),
+];
+
+// Downtimes selected by their attributes are listed first and the listed downtimes are removed
+// by name, so exactly the reported downtimes are removed
+fn remove_by_attributes(
+    cfg: &configuration::Configuration,
+    opt: &clap::ArgMatches,
+    selector: &command::filter::ObjectSelector,
+    author: String,
+) -> Result<(), Box<dyn Error>> {
+    let user = if opt.is_present("mine") {
+        match users::get_effective_username() {
+            Some(u) => match u.into_string() {
+                Ok(us) => Some(us),
+                Err(e) => bail!("Can't convert effective user name into a String: {:?}", e),
+            },
+            None => bail!("Can't get effective user name from operating system"),
+        }
+    } else {
+        None
+    };
+
+    let times: Vec<Option<&str>> = TIME_OPTIONS
+        .iter()
+        .map(|(option, _, _)| opt.value_of(option))
+        .collect();
+
+    let filter = build_attribute_filter(
+        selector,
+        opt.value_of("created_by"),
+        user.as_deref(),
+        opt.value_of("comment"),
+        &time_window(&times)?,
+    );
+
+    let downtimes = command::list_downtimes::get_downtimes(cfg, filter)?;
+    if downtimes.is_empty() {
+        println!("{}No matching downtimes found", util::instance_prefix(cfg));
+        return Ok(());
+    }
+
+    let names: Vec<String> = downtimes.iter().map(|d| d.name.clone()).collect();
+    let mut filter = command::filter::Filter::new();
+    let var = filter.add_var("downtime.names", serde_json::Value::from(names));
+    filter.add(&format!("downtime.__name in {}", var));
+
+    let payload = serde_json::to_string(&json_data::DelDowntimeRequest {
+        downtime: String::new(),
+        author,
+        object_type: "Downtime".to_string(),
+        filter: filter.expression(),
+        filter_vars: filter.vars(),
+    })?;
+
+    if cfg.debug {
+        eprintln!("HTTP method: POST");
+        eprintln!("URL: {}{}", cfg.url, constants::ICINGA2_DEL_DOWNTIME);
+        eprintln!("Payload: {}", payload);
+    }
+
+    if !command::objects::preflight(
+        cfg,
+        constants::ICINGA2_DEL_DOWNTIME,
+        "Downtime",
+        &filter,
+        &payload,
+    )? {
+        return Ok(());
+    }
+
+    let req = request::build_client(cfg, "")?
+        .post(
+            format!(
+                "{url}{path}",
+                url = cfg.url,
+                path = constants::ICINGA2_DEL_DOWNTIME,
+            )
+            .as_str(),
+        )
+        .body(payload)
+        .send()?;
+
+    // Icinga2 reports 500 if a downtime can't be removed, the reason is part of the results
+    if req.status() != StatusCode::OK && req.status() != StatusCode::INTERNAL_SERVER_ERROR {
+        let reason = req.status().canonical_reason().unwrap_or("None");
+        bail!(
+            "Invalid status code received, exepected HTTP status code 200 or 500, got \"{}\" instead",
+            reason
+        );
+    }
+
+    let raw = req.text()?;
+    let action_result: json_data::Icinga2ActionReply = serde_json::from_str(&raw)?;
+
+    let mut removed = 0;
+    for ar in &action_result.results {
+        if ar.code.round() as i64 == 200 {
+            removed += 1;
+        }
+        println!("{}{}", util::instance_prefix(cfg), ar.status);
+    }
+
+    if removed < downtimes.len() {
+        bail!(
+            "Only {} of {} downtime(s) were removed",
+            removed,
+            downtimes.len()
+        );
+    }
+
+    println!(
+        "{}Removed {} downtime(s):",
+        util::instance_prefix(cfg),
+        removed
+    );
+    command::list_downtimes::show(cfg, &downtimes)
+}
+
+// Filter expressions for the times of TIME_OPTIONS, an empty time window is rejected
+fn time_window(times: &[Option<&str>]) -> Result<Vec<String>, Box<dyn Error>> {
+    let mut parsed = Vec::new();
+    for ((_, name, _), time) in TIME_OPTIONS.iter().zip(times) {
+        parsed.push(match time {
+            Some(v) => match time_parser::parse_time(v) {
+                Ok(t) => Some(t),
+                Err(e) => bail!("Can't parse time for --{}: {}", name, e),
+            },
+            None => None,
+        });
+    }
+
+    // before and after of the same time attribute
+    for (before, after) in &[(0, 1), (2, 3)] {
+        if let (Some(b), Some(a)) = (parsed[*before], parsed[*after]) {
+            if a >= b {
+                bail!(
+                    "--{} must be before --{}",
+                    TIME_OPTIONS[*after].1,
+                    TIME_OPTIONS[*before].1
+                );
+            }
+        }
+    }
+
+    Ok(TIME_OPTIONS
+        .iter()
+        .zip(parsed)
+        .filter_map(|((_, _, expression), t)| t.map(|t| format!("{} {}", expression, t)))
+        .collect())
+}
+
+fn build_attribute_filter(
+    selector: &command::filter::ObjectSelector,
+    created_by: Option<&str>,
+    user: Option<&str>,
+    comment: Option<&str>,
+    times: &[String],
+) -> command::filter::Filter {
+    let mut filter = if selector.is_empty() {
+        command::filter::Filter::new()
+    } else {
+        let (obj_type, mut f) = command::filter::build_object_filter(selector);
+        // Like removal by host, a host without a service selects host downtimes only
+        if obj_type == "Host" {
+            f.add("downtime.service_name == \"\"");
+        }
+        f
+    };
+
+    if let Some(v) = created_by {
+        filter.add_match("downtime.author", v);
+    }
+
+    if let Some(v) = user {
+        let var = filter.add_var("downtime.author", serde_json::Value::from(v));
+        filter.add(&format!("downtime.author == {}", var));
+    }
+
+    if let Some(v) = comment {
+        let var = filter.add_var("downtime.comment", serde_json::Value::from(v));
+        filter.add(&format!("regex({}, downtime.comment)", var));
+    }
+
+    for t in times {
+        filter.add(t);
+    }
+
+    filter
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn time_windows() {
+        assert!(time_window(&[None, None, None, None]).unwrap().is_empty());
+
+        assert_eq!(
+            time_window(&[
+                Some("2021-01-02T00:00:00+00:00"),
+                Some("2021-01-01T00:00:00+00:00"),
+                None,
+                Some("2020-12-31T00:00:00+00:00"),
+            ])
+            .unwrap(),
+            vec![
+                "downtime.end_time < 1609545600",
+                "downtime.end_time > 1609459200",
+                "downtime.entry_time >= 1609372800",
+            ]
+        );
+
+        // empty time windows
+        assert!(time_window(&[
+            Some("2021-01-01T00:00:00+00:00"),
+            Some("2021-01-01T00:00:00+00:00"),
+            None,
+            None
+        ])
+        .is_err());
+        assert!(time_window(&[None, None, Some("-2h"), Some("-1h")]).is_err());
+
+        assert!(time_window(&[Some("yesterday noon"), None, None, None]).is_err());
+    }
+
+    #[test]
+    fn attribute_filter() {
+        let filter = build_attribute_filter(
+            &command::filter::ObjectSelector::default(),
+            None,
+            None,
+            None,
+            &[],
+        );
+        assert_eq!(filter.expression(), "");
+
+        let selector = command::filter::ObjectSelector {
+            hosts: "web*".to_string(),
+            ..Default::default()
+        };
+        let (expr, vars) = build_attribute_filter(
+            &selector,
+            Some("adm*"),
+            Some("root"),
+            Some("patch"),
+            &["downtime.end_time < 1609545600".to_string()],
+        )
+        .into_parts();
+        assert_eq!(
+            expr,
+            "match(icinga2ctl_host_name, host.name) && downtime.service_name == \"\" && match(icinga2ctl_downtime_author, downtime.author) && downtime.author == icinga2ctl_downtime_author_2 && regex(icinga2ctl_downtime_comment, downtime.comment) && downtime.end_time < 1609545600"
+        );
+        assert_eq!(vars["icinga2ctl_host_name"], "web*");
+        assert_eq!(vars["icinga2ctl_downtime_author"], "adm*");
+        assert_eq!(vars["icinga2ctl_downtime_author_2"], "root");
+        assert_eq!(vars["icinga2ctl_downtime_comment"], "patch");
+
+        let selector = command::filter::ObjectSelector {
+            services: "http".to_string(),
+            ..Default::default()
+        };
+        let filter = build_attribute_filter(&selector, None, None, None, &[]);
+        assert_eq!(
+            filter.expression(),
+            "match(icinga2ctl_service_name, service.name)"
+        );
+    }
+}
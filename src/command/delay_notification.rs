@@ -0,0 +1,120 @@
+use crate::command;
+use crate::configuration;
+use crate::constants;
+use crate::json_data;
+use crate::request;
+use crate::time_parser;
+use crate::usage;
+use crate::util;
+
+use http::StatusCode;
+use std::collections::HashMap;
+use std::error::Error;
+
+pub fn run(
+    cfg: &configuration::Configuration,
+    opt: &clap::ArgMatches,
+) -> Result<(), Box<dyn Error>> {
+    let mut state_map = HashMap::new();
+
+    if opt.is_present("help") {
+        usage::version::show();
+        usage::delay_notification::show();
+        return Ok(());
+    };
+
+    let selector = command::filter::ObjectSelector::from_options(opt);
+
+    let timestamp = match opt.value_of("until") {
+        Some(v) => match time_parser::parse_time(v) {
+            Ok(t) => t,
+            Err(e) => bail!("Can't parse time: {}", e),
+        },
+        None => bail!("Time to delay the notifications to is mandatory"),
+    };
+
+    if timestamp <= chrono::Local::now().timestamp() {
+        bail!("Notifications can't be delayed to the past");
+    }
+
+    if opt.is_present("warning") {
+        state_map.insert("==1".to_string(), String::new());
+    }
+    if opt.is_present("critical") {
+        state_map.insert("==2".to_string(), String::new());
+    }
+    if opt.is_present("unknown") {
+        state_map.insert("==3".to_string(), String::new());
+    }
+
+    if selector.is_empty() {
+        bail!("Provide a host, service, group and/or filter to delay notifications for");
+    }
+
+    let (delay_type, mut filter) = command::filter::build_object_filter(&selector);
+    if !state_map.is_empty() {
+        filter.add(&command::filter::build_state_filter(
+            &delay_type.to_lowercase(),
+            &state_map,
+        ));
+    }
+
+    let payload = serde_json::to_string(&json_data::DelayNotificationRequest {
+        object_type: delay_type.to_string(),
+        filter: filter.expression(),
+        filter_vars: filter.vars(),
+        timestamp,
+    })?;
+
+    if cfg.debug {
+        eprintln!("HTTP method: POST");
+        eprintln!("URL: {}{}", cfg.url, constants::ICINGA2_DELAY_NOTIFICATION);
+        eprintln!("Payload: {}", payload);
+    }
+
+    if !command::objects::preflight(
+        cfg,
+        constants::ICINGA2_DELAY_NOTIFICATION,
+        delay_type,
+        &filter,
+        &payload,
+    )? {
+        return Ok(());
+    }
+
+    let req = request::build_client(cfg, "")?
+        .post(
+            format!(
+                "{url}{path}",
+                url = cfg.url,
+                path = constants::ICINGA2_DELAY_NOTIFICATION,
+            )
+            .as_str(),
+        )
+        .body(payload)
+        .send()?;
+
+    if req.status() != StatusCode::OK && req.status() != StatusCode::INTERNAL_SERVER_ERROR {
+        let reason = req.status().canonical_reason().unwrap_or("None");
+        bail!(
+            "Invalid status code received, exepected HTTP status code 200 or 500, got \"{}\" instead",
+            reason
+        );
+    }
+
+    let raw = req.text()?;
+    let action_result: json_data::Icinga2ActionReply = serde_json::from_str(&raw)?;
+
+    for ar in action_result.results {
+        match ar.name {
+            Some(v) => {
+                println!("{}{}: {}", util::instance_prefix(cfg), v, ar.status);
+            }
+            None => {
+                println!("{}{}", util::instance_prefix(cfg), ar.status);
+            }
+        };
+    }
+
+    Ok(())
+}
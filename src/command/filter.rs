@@ -1,6 +1,149 @@
 use crate::constants;
+use crate::json_data;
+
 use std::collections::HashMap;
 
+// Filter expression for the Icinga2 API.
+// User supplied values are never spliced into the expression, they are passed as filter variables
+// (filter_vars) and the expression only references the variables.
+pub struct Filter {
+    expressions: Vec<String>,
+    vars: json_data::FilterVars,
+}
+
+impl Filter {
+    pub fn new() -> Filter {
+        Filter {
+            expressions: Vec::new(),
+            vars: json_data::FilterVars::new(),
+        }
+    }
+
+    // Add an expression, the expression must not contain user supplied values
+    pub fn add(&mut self, expression: &str) {
+        self.expressions.push(expression.to_string());
+    }
+
+    // Add a filter expression supplied by the user (--filter). It is combined with the other
+    // expressions and can reference all attributes of the objects, e.g. host.vars.os
+    pub fn add_expression(&mut self, expression: &str) {
+        if !expression.is_empty() {
+            self.expressions.push(format!("({})", expression));
+        }
+    }
+
+    // Add a variable and return its name. The name is derived from the attribute the value
+    // is compared with, e.g. host.name -> host_name
+    pub fn add_var(&mut self, attribute: &str, value: serde_json::Value) -> String {
+        let base: String = attribute
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect();
+        let base = format!("{}_{}", constants::NAME, base.trim_matches('_'));
+
+        let mut name = base.clone();
+        let mut i = 1;
+        while self.vars.contains_key(&name) {
+            i += 1;
+            name = format!("{}_{}", base, i);
+        }
+
+        self.vars.insert(name.clone(), value);
+        name
+    }
+
+    // match(<pattern>, <attribute>)
+    pub fn add_match(&mut self, attribute: &str, pattern: &str) {
+        let var = self.add_var(attribute, serde_json::Value::from(pattern));
+        self.add(&format!("match({}, {})", var, attribute));
+    }
+
+    // match(<pattern>, <attribute>, MatchAny) for array attributes, e.g. host.groups
+    pub fn add_match_any(&mut self, attribute: &str, pattern: &str) {
+        let var = self.add_var(attribute, serde_json::Value::from(pattern));
+        self.add(&format!("match({}, {}, MatchAny)", var, attribute));
+    }
+
+    pub fn expression(&self) -> String {
+        self.expressions.join(" && ")
+    }
+
+    pub fn vars(&self) -> json_data::FilterVars {
+        self.vars.clone()
+    }
+
+    // Returns the filter expression and the filter variables for the request payload
+    pub fn into_parts(self) -> (String, json_data::FilterVars) {
+        (self.expression(), self.vars)
+    }
+}
+
+// Host and service objects selected by the command line options -H/--host, -S/--service,
+// --hostgroup, --servicegroup and --filter
+#[derive(Default)]
+pub struct ObjectSelector {
+    pub hosts: String,
+    pub services: String,
+    pub hostgroups: String,
+    pub servicegroups: String,
+    pub expression: String,
+}
+
+impl ObjectSelector {
+    pub fn from_options(opt: &clap::ArgMatches) -> ObjectSelector {
+        let value = |name: &str| match opt.value_of(name) {
+            Some(v) => v.to_string(),
+            None => String::new(),
+        };
+
+        ObjectSelector {
+            hosts: value("host_object"),
+            services: value("service_object"),
+            hostgroups: value("hostgroup"),
+            servicegroups: value("servicegroup"),
+            expression: value("filter"),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.hosts.is_empty()
+            && self.services.is_empty()
+            && self.hostgroups.is_empty()
+            && self.servicegroups.is_empty()
+            && self.expression.is_empty()
+    }
+
+    // Services are selected only if a service or a service group was given
+    pub fn object_type(&self) -> &'static str {
+        if self.services.is_empty() && self.servicegroups.is_empty() {
+            "Host"
+        } else {
+            "Service"
+        }
+    }
+}
+
+// Build the filter for the selected objects. Returns the object type (Host or Service) and the filter.
+pub fn build_object_filter(sel: &ObjectSelector) -> (&'static str, Filter) {
+    let mut filter = Filter::new();
+
+    if !sel.hosts.is_empty() {
+        filter.add_match("host.name", &sel.hosts);
+    }
+    if !sel.services.is_empty() {
+        filter.add_match("service.name", &sel.services);
+    }
+    if !sel.hostgroups.is_empty() {
+        filter.add_match_any("host.groups", &sel.hostgroups);
+    }
+    if !sel.servicegroups.is_empty() {
+        filter.add_match_any("service.groups", &sel.servicegroups);
+    }
+    filter.add_expression(&sel.expression);
+
+    (sel.object_type(), filter)
+}
+
 pub fn build_state_filter(n: &str, m: &HashMap<String, String>) -> String {
     if !m.is_empty() {
         let mut v = Vec::new();
@@ -20,3 +163,125 @@ pub fn build_ack_filter(n: &str, ack: i8) -> String {
         _ => format!("{}.acknowledgement >= 0", n),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_filter() {
+        let filter = Filter::new();
+        let (expr, vars) = filter.into_parts();
+        assert_eq!(expr, "");
+        assert!(vars.is_empty());
+    }
+
+    #[test]
+    fn user_input_is_passed_as_variable() {
+        let pattern = "web*\", host.name) || true || match(\"\\\n";
+        let mut filter = Filter::new();
+        filter.add_match("host.name", pattern);
+
+        let (expr, vars) = filter.into_parts();
+        assert_eq!(expr, "match(icinga2ctl_host_name, host.name)");
+        assert_eq!(vars["icinga2ctl_host_name"], pattern);
+    }
+
+    #[test]
+    fn variable_names_are_unique() {
+        let mut filter = Filter::new();
+        filter.add_match("comment.author", "a");
+        let var = filter.add_var("comment.author", serde_json::Value::from("b"));
+        filter.add(&format!("comment.author == {}", var));
+        filter.add("comment.entry_type == 1");
+
+        let (expr, vars) = filter.into_parts();
+        assert_eq!(
+            expr,
+            "match(icinga2ctl_comment_author, comment.author) && comment.author == icinga2ctl_comment_author_2 && comment.entry_type == 1"
+        );
+        assert_eq!(vars["icinga2ctl_comment_author"], "a");
+        assert_eq!(vars["icinga2ctl_comment_author_2"], "b");
+    }
+
+    fn selector(hosts: &str, services: &str) -> ObjectSelector {
+        ObjectSelector {
+            hosts: hosts.to_string(),
+            services: services.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn object_filter() {
+        let (t, f) = build_object_filter(&selector("h*", ""));
+        assert_eq!(t, "Host");
+        assert_eq!(f.expression(), "match(icinga2ctl_host_name, host.name)");
+
+        let (t, f) = build_object_filter(&selector("", "s*"));
+        assert_eq!(t, "Service");
+        assert_eq!(
+            f.expression(),
+            "match(icinga2ctl_service_name, service.name)"
+        );
+
+        let (t, f) = build_object_filter(&selector("h*", "s*"));
+        assert_eq!(t, "Service");
+        assert_eq!(
+            f.expression(),
+            "match(icinga2ctl_host_name, host.name) && match(icinga2ctl_service_name, service.name)"
+        );
+
+        let mut sel = selector("", "s*");
+        sel.expression = "service.vars.sla == 1 || host.zone == \"dmz\"".to_string();
+        let (t, f) = build_object_filter(&sel);
+        assert_eq!(t, "Service");
+        assert_eq!(
+            f.expression(),
+            "match(icinga2ctl_service_name, service.name) && (service.vars.sla == 1 || host.zone == \"dmz\")"
+        );
+
+        let mut sel = selector("", "");
+        assert!(sel.is_empty());
+        sel.expression = "\"linux\" in host.groups".to_string();
+        assert!(!sel.is_empty());
+        let (t, f) = build_object_filter(&sel);
+        assert_eq!(t, "Host");
+        assert_eq!(f.expression(), "(\"linux\" in host.groups)");
+    }
+
+    #[test]
+    fn group_filter() {
+        let mut sel = selector("", "");
+        sel.hostgroups = "linux-*".to_string();
+        let (t, f) = build_object_filter(&sel);
+        assert_eq!(t, "Host");
+        let (expr, vars) = f.into_parts();
+        assert_eq!(expr, "match(icinga2ctl_host_groups, host.groups, MatchAny)");
+        assert_eq!(vars["icinga2ctl_host_groups"], "linux-*");
+
+        sel.servicegroups = "db\"*".to_string();
+        let (t, f) = build_object_filter(&sel);
+        assert_eq!(t, "Service");
+        let (expr, vars) = f.into_parts();
+        assert_eq!(
+            expr,
+            "match(icinga2ctl_host_groups, host.groups, MatchAny) && match(icinga2ctl_service_groups, service.groups, MatchAny)"
+        );
+        assert_eq!(vars["icinga2ctl_service_groups"], "db\"*");
+    }
+
+    #[test]
+    fn state_and_ack_filter() {
+        let mut m = HashMap::new();
+        assert_eq!(build_state_filter("host", &m), "host.state >= 0");
+        m.insert("==2".to_string(), String::new());
+        assert_eq!(build_state_filter("service", &m), "(service.state ==2)");
+
+        assert_eq!(
+            build_ack_filter("host", constants::ICINGA2_ACK_STICKY),
+            "host.acknowledgement == 2"
+        );
+        assert_eq!(build_ack_filter("host", -1), "host.acknowledgement >= 0");
+    }
+}
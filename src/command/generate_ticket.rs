@@ -1,8 +1,10 @@
+use crate::command;
 use crate::configuration;
 use crate::constants;
 use crate::json_data;
 use crate::request;
 use crate::usage;
+use crate::util;
 
 use http::StatusCode;
 use std::error::Error;
@@ -24,7 +26,7 @@ pub fn run(
         }
     };
 
-    let payload = format!("{{\"cn\":\"{}\"}}", cn);
+    let payload = serde_json::to_string(&json_data::GenerateTicketRequest { cn: cn.to_string() })?;
 
     if cfg.debug {
         eprintln!("HTTP method: POST");
@@ -32,6 +34,16 @@ pub fn run(
         eprintln!("Payload: {}", payload);
     }
 
+    if !command::objects::preflight(
+        cfg,
+        constants::ICINGA2_GENERATE_TICKET,
+        "",
+        &command::filter::Filter::new(),
+        &payload,
+    )? {
+        return Ok(());
+    }
+
     let req = request::build_client(cfg, "")?
         .post(
             format!(
@@ -67,10 +79,10 @@ pub fn run(
     for ar in action_result.results {
         match ar.ticket {
             Some(v) => {
-                println!("{}: {}", v, ar.status);
+                println!("{}{}: {}", util::instance_prefix(cfg), v, ar.status);
             }
             None => {
-                println!("-: {}", ar.status);
+                println!("{}-: {}", util::instance_prefix(cfg), ar.status);
             }
         };
     }
@@ -0,0 +1,29 @@
+use crate::command;
+use crate::configuration;
+use crate::constants;
+use crate::output;
+use crate::usage;
+
+use std::error::Error;
+
+pub fn run(
+    cfg: &configuration::Configuration,
+    opt: &clap::ArgMatches,
+) -> Result<(), Box<dyn Error>> {
+    if opt.is_present("help") {
+        usage::version::show();
+        usage::list_acks::show();
+        return Ok(());
+    }
+
+    let records = query(cfg, opt)?;
+    command::list_comments::show(cfg, &records)
+}
+
+pub fn query(
+    cfg: &configuration::Configuration,
+    opt: &clap::ArgMatches,
+) -> Result<Vec<output::CommentRecord>, Box<dyn Error>> {
+    // Acknowledgements are stored as comments of entry type acknowledgement
+    command::list_comments::query_type(cfg, opt, constants::ICINGA2_COMMENT_ACK)
+}
@@ -0,0 +1,217 @@
+use crate::command;
+use crate::configuration;
+use crate::constants;
+use crate::json_data;
+use crate::output;
+use crate::request;
+use crate::usage;
+use crate::util;
+
+use http::StatusCode;
+use std::error::Error;
+
+pub fn run(
+    cfg: &configuration::Configuration,
+    opt: &clap::ArgMatches,
+) -> Result<(), Box<dyn Error>> {
+    if opt.is_present("help") {
+        usage::version::show();
+        usage::list_comments::show();
+        return Ok(());
+    }
+
+    let entry_type = parse_entry_type(opt)?;
+    let records = query_type(cfg, opt, entry_type)?;
+    show(cfg, &records)
+}
+
+pub fn query(
+    cfg: &configuration::Configuration,
+    opt: &clap::ArgMatches,
+) -> Result<Vec<output::CommentRecord>, Box<dyn Error>> {
+    let entry_type = parse_entry_type(opt)?;
+    query_type(cfg, opt, entry_type)
+}
+
+fn parse_entry_type(opt: &clap::ArgMatches) -> Result<i8, Box<dyn Error>> {
+    match opt.value_of("type") {
+        Some(v) => match v {
+            "user" => Ok(constants::ICINGA2_COMMENT_USER),
+            "downtime" => Ok(constants::ICINGA2_COMMENT_DOWNTIME),
+            "flapping" => Ok(constants::ICINGA2_COMMENT_FLAPPING),
+            "ack" => Ok(constants::ICINGA2_COMMENT_ACK),
+            _ => {
+                bail!("Invalid comment type {}", v);
+            }
+        },
+        None => Ok(-1),
+    }
+}
+
+pub fn query_type(
+    cfg: &configuration::Configuration,
+    opt: &clap::ArgMatches,
+    entry_type: i8,
+) -> Result<Vec<output::CommentRecord>, Box<dyn Error>> {
+    let mut filter = command::filter::Filter::new();
+
+    let hosts = match opt.value_of("host_object") {
+        Some(v) => v.to_string(),
+        None => String::new(),
+    };
+    let services = match opt.value_of("service_object") {
+        Some(v) => v.to_string(),
+        None => String::new(),
+    };
+    let expression = match opt.value_of("filter") {
+        Some(v) => v.to_string(),
+        None => String::new(),
+    };
+    let author = match opt.value_of("author") {
+        Some(v) => v.to_string(),
+        None => String::new(),
+    };
+
+    // Like del-comment, a host filter without a service filter selects host comments only
+    if !hosts.is_empty() {
+        filter.add_match("comment.host_name", &hosts);
+        if services.is_empty() {
+            filter.add("comment.service_name == \"\"");
+        }
+    }
+
+    if !services.is_empty() {
+        filter.add_match("comment.service_name", &services);
+    }
+
+    if !author.is_empty() {
+        filter.add_match("comment.author", &author);
+    }
+
+    if entry_type >= 0 {
+        filter.add(&format!("comment.entry_type == {}", entry_type));
+    }
+
+    filter.add_expression(&expression);
+
+    let (filter, filter_vars) = filter.into_parts();
+    let payload = serde_json::to_string(&json_data::Icinga2ObjectQuery {
+        filter,
+        filter_vars,
+    })?;
+
+    let attrs = "attrs=author&attrs=entry_time&attrs=entry_type&attrs=expire_time&attrs=host_name&attrs=service_name&attrs=text";
+
+    if cfg.debug {
+        eprintln!("HTTP method: GET");
+        eprintln!(
+            "URL: {}{}?{}",
+            cfg.url,
+            constants::ICINGA2_OBJ_COMMENT,
+            attrs
+        );
+        eprintln!("Payload: {}", payload);
+    }
+
+    let req = request::build_client(cfg, "GET")?
+        .post(
+            format!(
+                "{url}{path}?{attrs}",
+                url = cfg.url,
+                path = constants::ICINGA2_OBJ_COMMENT,
+                attrs = attrs
+            )
+            .as_str(),
+        )
+        .body(payload)
+        .send()?;
+
+    if req.status() != StatusCode::OK {
+        let reason = req.status().canonical_reason().unwrap_or("None");
+        bail!(
+            "Invalid status code received, exepected \"200 OK\", got \"{}\" instead",
+            reason
+        );
+    }
+
+    let raw = req.text()?;
+
+    let comments: json_data::Icinga2Comments = match serde_json::from_str(raw.as_str()) {
+        Ok(v) => v,
+        Err(e) => {
+            return Err(Box::new(e));
+        }
+    };
+
+    let mut records = Vec::new();
+
+    for c in comments.results {
+        records.push(output::CommentRecord {
+            instance: cfg.instance.clone(),
+            name: c.name,
+            host: c.attrs.host_name,
+            service: c.attrs.service_name,
+            author: c.attrs.author,
+            entry_type: util::comment_type_to_string(c.attrs.entry_type),
+            timestamp: util::timestamp_to_rfc3339(c.attrs.entry_time),
+            expire: util::timestamp_to_rfc3339(c.attrs.expire_time),
+            text: c.attrs.text,
+        });
+    }
+
+    records.sort_by(|a, b| a.name.cmp(&b.name));
+
+    Ok(records)
+}
+
+pub fn show(
+    cfg: &configuration::Configuration,
+    records: &[output::CommentRecord],
+) -> Result<(), Box<dyn Error>> {
+    if cfg.output != constants::OUTPUT_TEXT {
+        return output::print_records(records, cfg.output);
+    }
+
+    let mut rows = Vec::new();
+    for r in records {
+        let object = if r.service.is_empty() {
+            r.host.clone()
+        } else {
+            format!("{}!{}", r.host, r.service)
+        };
+
+        let expire = if r.expire.is_empty() {
+            "-".to_string()
+        } else {
+            r.expire.clone()
+        };
+
+        rows.push(vec![
+            r.instance.clone(),
+            r.name.clone(),
+            object,
+            r.author.clone(),
+            r.entry_type.clone(),
+            r.timestamp.clone(),
+            expire,
+            r.text.replace("\n", " "),
+        ]);
+    }
+
+    util::print_instance_table(
+        cfg,
+        &[
+            "INSTANCE",
+            "NAME",
+            "OBJECT",
+            "AUTHOR",
+            "TYPE",
+            "TIMESTAMP",
+            "EXPIRE",
+            "TEXT",
+        ],
+        &rows,
+    );
+
+    Ok(())
+}
@@ -0,0 +1,203 @@
+use crate::command;
+use crate::configuration;
+use crate::constants;
+use crate::json_data;
+use crate::output;
+use crate::time_parser;
+use crate::usage;
+use crate::util;
+
+use std::error::Error;
+
+pub fn run(
+    cfg: &configuration::Configuration,
+    opt: &clap::ArgMatches,
+) -> Result<(), Box<dyn Error>> {
+    if opt.is_present("help") {
+        usage::version::show();
+        usage::list_downtimes::show();
+        return Ok(());
+    }
+
+    let records = query(cfg, opt)?;
+    show(cfg, &records)
+}
+
+pub fn query(
+    cfg: &configuration::Configuration,
+    opt: &clap::ArgMatches,
+) -> Result<Vec<output::DowntimeRecord>, Box<dyn Error>> {
+    let mut filter = command::filter::Filter::new();
+
+    let hosts = match opt.value_of("host_object") {
+        Some(v) => v.to_string(),
+        None => String::new(),
+    };
+    let services = match opt.value_of("service_object") {
+        Some(v) => v.to_string(),
+        None => String::new(),
+    };
+    let expression = match opt.value_of("filter") {
+        Some(v) => v.to_string(),
+        None => String::new(),
+    };
+    let author = match opt.value_of("author") {
+        Some(v) => v.to_string(),
+        None => String::new(),
+    };
+
+    if opt.is_present("fixed") && opt.is_present("flexible") {
+        bail!("Listing only fixed and only flexible downtimes are mutually exclusive");
+    }
+
+    // Like del-downtime, a host filter without a service filter selects host downtimes only
+    if !hosts.is_empty() {
+        filter.add_match("downtime.host_name", &hosts);
+        if services.is_empty() {
+            filter.add("downtime.service_name == \"\"");
+        }
+    }
+
+    if !services.is_empty() {
+        filter.add_match("downtime.service_name", &services);
+    }
+
+    if !author.is_empty() {
+        filter.add_match("downtime.author", &author);
+    }
+
+    if opt.is_present("active") {
+        filter.add("downtime.is_in_effect");
+    }
+
+    if opt.is_present("fixed") {
+        filter.add("downtime.fixed");
+    }
+
+    if opt.is_present("flexible") {
+        filter.add("!downtime.fixed");
+    }
+
+    // Select downtimes overlapping with the requested time window
+    if let Some(v) = opt.value_of("start") {
+        match time_parser::parse_time(v) {
+            Ok(v) => filter.add(&format!("downtime.end_time >= {}", v)),
+            Err(e) => bail!("Can't parse start time: {}", e),
+        };
+    }
+
+    if let Some(v) = opt.value_of("end") {
+        match time_parser::parse_time(v) {
+            Ok(v) => filter.add(&format!("downtime.start_time <= {}", v)),
+            Err(e) => bail!("Can't parse end time: {}", e),
+        };
+    }
+
+    filter.add_expression(&expression);
+
+    get_downtimes(cfg, filter)
+}
+
+// Query the downtimes matching the filter
+pub fn get_downtimes(
+    cfg: &configuration::Configuration,
+    filter: command::filter::Filter,
+) -> Result<Vec<output::DowntimeRecord>, Box<dyn Error>> {
+    let downtimes = query_downtimes(cfg, filter)?;
+
+    let mut records = Vec::new();
+
+    for d in downtimes.results {
+        // flexible downtimes have a duration, the duration of fixed downtimes is the downtime window
+        let duration = if d.attrs.fixed {
+            (d.attrs.end_time - d.attrs.start_time).round() as i64
+        } else {
+            d.attrs.duration.round() as i64
+        };
+
+        records.push(output::DowntimeRecord {
+            instance: cfg.instance.clone(),
+            name: d.name,
+            host: d.attrs.host_name,
+            service: d.attrs.service_name,
+            author: d.attrs.author,
+            start: util::timestamp_to_rfc3339(d.attrs.start_time),
+            end: util::timestamp_to_rfc3339(d.attrs.end_time),
+            duration,
+            fixed: d.attrs.fixed,
+            active: d.attrs.is_in_effect,
+            comment: d.attrs.comment,
+            trigger: d.attrs.triggered_by,
+        });
+    }
+
+    records.sort_by(|a, b| a.name.cmp(&b.name));
+
+    Ok(records)
+}
+
+// Query the downtime objects matching the filter
+pub fn query_downtimes(
+    cfg: &configuration::Configuration,
+    filter: command::filter::Filter,
+) -> Result<json_data::Icinga2Downtimes, Box<dyn Error>> {
+    let (filter, filter_vars) = filter.into_parts();
+    let payload = serde_json::to_string(&json_data::Icinga2ObjectQuery {
+        filter,
+        filter_vars,
+    })?;
+
+    command::objects::get_objects(
+        cfg,
+        constants::ICINGA2_OBJ_DOWNTIME,
+        "attrs=author&attrs=comment&attrs=duration&attrs=end_time&attrs=entry_time&attrs=fixed&attrs=host_name&attrs=is_in_effect&attrs=service_name&attrs=start_time&attrs=triggered_by",
+        payload,
+    )
+}
+
+pub fn show(
+    cfg: &configuration::Configuration,
+    records: &[output::DowntimeRecord],
+) -> Result<(), Box<dyn Error>> {
+    if cfg.output != constants::OUTPUT_TEXT {
+        return output::print_records(records, cfg.output);
+    }
+
+    let mut rows = Vec::new();
+    for r in records {
+        let object = if r.service.is_empty() {
+            r.host.clone()
+        } else {
+            format!("{}!{}", r.host, r.service)
+        };
+
+        let trigger = if r.trigger.is_empty() {
+            "-".to_string()
+        } else {
+            r.trigger.clone()
+        };
+
+        rows.push(vec![
+            r.instance.clone(),
+            r.name.clone(),
+            object,
+            r.author.clone(),
+            r.start.clone(),
+            r.end.clone(),
+            util::format_duration(r.duration),
+            trigger,
+            r.comment.replace("\n", " "),
+        ]);
+    }
+
+    util::print_instance_table(
+        cfg,
+        &[
+            "INSTANCE", "NAME", "OBJECT", "AUTHOR", "START", "END", "DURATION", "TRIGGER",
+            "COMMENT",
+        ],
+        &rows,
+    );
+
+    Ok(())
+}
@@ -0,0 +1,213 @@
+use crate::command;
+use crate::configuration;
+use crate::constants;
+use crate::json_data;
+use crate::output;
+use crate::usage;
+use crate::util;
+
+use std::collections::HashMap;
+use std::error::Error;
+
+pub fn run(
+    cfg: &configuration::Configuration,
+    opt: &clap::ArgMatches,
+) -> Result<(), Box<dyn Error>> {
+    if opt.is_present("help") {
+        usage::version::show();
+        usage::list_groups::show();
+        return Ok(());
+    }
+
+    let records = query(cfg, opt)?;
+    show(cfg, &records)
+}
+
+pub fn query(
+    cfg: &configuration::Configuration,
+    opt: &clap::ArgMatches,
+) -> Result<Vec<output::GroupRecord>, Box<dyn Error>> {
+    let (hostgroups, servicegroups) = match opt.value_of("type") {
+        Some(v) => match v {
+            "host" => (true, false),
+            "service" => (false, true),
+            _ => {
+                bail!("Invalid group type {}", v);
+            }
+        },
+        None => (true, true),
+    };
+
+    let group = match opt.value_of("group") {
+        Some(v) => v.to_string(),
+        None => String::new(),
+    };
+
+    let mut records = Vec::new();
+
+    if hostgroups {
+        records.extend(query_groups(
+            cfg,
+            "hostgroup",
+            &group,
+            constants::ICINGA2_OBJ_HOSTGROUP,
+            constants::ICINGA2_OBJ_HOST,
+        )?);
+    }
+
+    if servicegroups {
+        records.extend(query_groups(
+            cfg,
+            "servicegroup",
+            &group,
+            constants::ICINGA2_OBJ_SERVICEGROUP,
+            constants::ICINGA2_OBJ_SERVICE,
+        )?);
+    }
+
+    Ok(records)
+}
+
+// Query groups and their members. Member counts and states are summarised from the groups
+// attribute of the member objects.
+fn query_groups(
+    cfg: &configuration::Configuration,
+    group_type: &str,
+    group: &str,
+    group_path: &str,
+    member_path: &str,
+) -> Result<Vec<output::GroupRecord>, Box<dyn Error>> {
+    let mut filter = command::filter::Filter::new();
+    if !group.is_empty() {
+        filter.add_match(&format!("{}.name", group_type), group);
+    }
+    let (filter, filter_vars) = filter.into_parts();
+    let payload = serde_json::to_string(&json_data::Icinga2ObjectQuery {
+        filter,
+        filter_vars,
+    })?;
+
+    let groups: json_data::Icinga2Groups =
+        command::objects::get_objects(cfg, group_path, "attrs=display_name", payload)?;
+
+    // Only fetch the members of the selected groups
+    let mut filter = command::filter::Filter::new();
+    if !group.is_empty() {
+        let member_type = if group_type == "hostgroup" {
+            "host"
+        } else {
+            "service"
+        };
+        filter.add_match_any(&format!("{}.groups", member_type), group);
+    }
+    let (filter, filter_vars) = filter.into_parts();
+    let payload = serde_json::to_string(&json_data::Icinga2ObjectQuery {
+        filter,
+        filter_vars,
+    })?;
+
+    let members: json_data::Icinga2GroupMembers =
+        command::objects::get_objects(cfg, member_path, "attrs=groups&attrs=state", payload)?;
+
+    // group name -> (members, problems, worst state)
+    let mut summary: HashMap<String, (u64, u64, i32)> = HashMap::new();
+    for m in members.results {
+        let state = if group_type == "hostgroup" {
+            // Hosts only know UP (0) and DOWN (1), a host in DOWN state is critical
+            match m.attrs.state.round() as i32 {
+                0 => constants::PLUGIN_OK,
+                _ => constants::PLUGIN_CRITICAL,
+            }
+        } else {
+            match m.attrs.state.round() as i32 {
+                0 => constants::PLUGIN_OK,
+                1 => constants::PLUGIN_WARNING,
+                2 => constants::PLUGIN_CRITICAL,
+                _ => constants::PLUGIN_UNKNOWN,
+            }
+        };
+
+        for g in m.attrs.groups {
+            let entry = summary.entry(g).or_insert((0, 0, constants::PLUGIN_OK));
+            entry.0 += 1;
+            if state != constants::PLUGIN_OK {
+                entry.1 += 1;
+            }
+            if command::status::plugin_state_severity(state)
+                > command::status::plugin_state_severity(entry.2)
+            {
+                entry.2 = state;
+            }
+        }
+    }
+
+    let mut records = Vec::new();
+    for g in groups.results {
+        let (members, problems, worst_state) = match summary.get(&g.name) {
+            Some((m, p, s)) => (
+                *m,
+                *p,
+                command::status::plugin_state_to_string(*s).to_string(),
+            ),
+            None => (0, 0, String::new()),
+        };
+
+        records.push(output::GroupRecord {
+            instance: cfg.instance.clone(),
+            group_type: group_type.to_string(),
+            name: g.name,
+            display_name: g.attrs.display_name,
+            members,
+            problems,
+            worst_state,
+        });
+    }
+
+    records.sort_by(|a, b| a.name.cmp(&b.name));
+
+    Ok(records)
+}
+
+pub fn show(
+    cfg: &configuration::Configuration,
+    records: &[output::GroupRecord],
+) -> Result<(), Box<dyn Error>> {
+    if cfg.output != constants::OUTPUT_TEXT {
+        return output::print_records(records, cfg.output);
+    }
+
+    let mut rows = Vec::new();
+    for r in records {
+        let worst_state = if r.worst_state.is_empty() {
+            "-".to_string()
+        } else {
+            r.worst_state.clone()
+        };
+
+        rows.push(vec![
+            r.instance.clone(),
+            r.group_type.clone(),
+            r.name.clone(),
+            r.display_name.clone(),
+            r.members.to_string(),
+            r.problems.to_string(),
+            worst_state,
+        ]);
+    }
+
+    util::print_instance_table(
+        cfg,
+        &[
+            "INSTANCE",
+            "TYPE",
+            "NAME",
+            "DISPLAY NAME",
+            "MEMBERS",
+            "PROBLEMS",
+            "WORST STATE",
+        ],
+        &rows,
+    );
+
+    Ok(())
+}
@@ -1,10 +1,24 @@
 pub mod add_ack;
 pub mod add_comment;
 pub mod add_downtime;
+pub mod config;
+pub mod config_package;
 pub mod del_ack;
 pub mod del_comment;
 pub mod del_downtime;
+pub mod delay_notification;
 pub mod filter;
 pub mod generate_ticket;
+pub mod list_acks;
+pub mod list_comments;
+pub mod list_downtimes;
+pub mod list_groups;
+pub mod modify_downtime;
+pub mod notifications;
+pub mod objects;
+pub mod process_check_result;
 pub mod reschedule_check;
+pub mod schedule_downtime;
+pub mod send_custom_notification;
+pub mod set_attr;
 pub mod status;
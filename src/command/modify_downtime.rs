@@ -0,0 +1,347 @@
+use crate::command;
+use crate::configuration;
+use crate::constants;
+use crate::json_data;
+use crate::request;
+use crate::time_parser;
+use crate::usage;
+use crate::util;
+
+use http::StatusCode;
+use std::error::Error;
+
+pub fn run(
+    cfg: &configuration::Configuration,
+    opt: &clap::ArgMatches,
+) -> Result<(), Box<dyn Error>> {
+    if opt.is_present("help") {
+        usage::version::show();
+        usage::modify_downtime::show();
+        return Ok(());
+    };
+
+    let selector = command::filter::ObjectSelector::from_options(opt);
+
+    let downtime = match opt.value_of("downtime_name") {
+        Some(v) => v.to_string(),
+        None => String::new(),
+    };
+
+    if selector.is_empty() && downtime.is_empty() {
+        bail!("Neither downtime name nor host/service/group/filter provided");
+    }
+
+    if !downtime.is_empty() && !selector.is_empty() {
+        bail!("Provide either a downtime name or a host/service/group/filter, but not both");
+    }
+
+    let end = match opt.value_of("end") {
+        Some(v) => match time_parser::parse_time(v) {
+            Ok(t) => Some(t),
+            Err(e) => bail!("Can't parse end time: {}", e),
+        },
+        None => None,
+    };
+
+    let extend = match opt.value_of("extend") {
+        Some(v) => match time_parser::parse_duration(v) {
+            Ok(d) => Some(d),
+            Err(e) => bail!("Can't parse extension of the downtime: {}", e),
+        },
+        None => None,
+    };
+
+    let duration = match opt.value_of("duration") {
+        Some(v) => match time_parser::parse_duration(v) {
+            Ok(d) if d > 0 => Some(d),
+            Ok(_) => bail!("A duration of 0 or less is rather pointless"),
+            Err(e) => bail!("Can't parse duration: {}", e),
+        },
+        None => None,
+    };
+
+    if end.is_some() && extend.is_some() {
+        bail!("A new end time and an extension of the downtime are mutually exclusive");
+    }
+
+    if end.is_none() && extend.is_none() && duration.is_none() {
+        bail!("Provide a new end time, an extension or a new duration of the downtime");
+    }
+
+    let author = match opt.value_of("author") {
+        Some(v) => v.to_string(),
+        None => String::new(),
+    };
+
+    // Like removal by host, a host without a service selects host downtimes only
+    let filter = if downtime.is_empty() {
+        let (obj_type, mut f) = command::filter::build_object_filter(&selector);
+        if obj_type == "Host" {
+            f.add("downtime.service_name == \"\"");
+        }
+        f
+    } else {
+        let mut f = command::filter::Filter::new();
+        let var = f.add_var("downtime.name", serde_json::Value::from(downtime.as_str()));
+        f.add(&format!("downtime.__name == {}", var));
+        f
+    };
+
+    let downtimes = command::list_downtimes::query_downtimes(cfg, filter)?;
+    if downtimes.results.is_empty() {
+        if downtime.is_empty() {
+            println!("{}No matching downtimes found", util::instance_prefix(cfg));
+        } else {
+            println!(
+                "{}Downtime {} does not exist",
+                util::instance_prefix(cfg),
+                downtime
+            );
+        }
+        return Ok(());
+    }
+
+    // Removing the old downtime removes its child downtimes too, replacing it would silently
+    // drop the coverage of the child objects. Downtimes triggered by the old downtime would
+    // never be triggered.
+    let all_names: Vec<String> = downtimes.results.iter().map(|d| d.name.clone()).collect();
+    let (parents, triggers) = dependent_downtimes(cfg, &all_names)?;
+    if !parents.is_empty() {
+        bail!(
+            "Downtime(s) {} have child downtimes, which would be removed together with the old downtime. Remove the downtime and schedule it again instead",
+            parents.join(", ")
+        );
+    }
+    if !triggers.is_empty() {
+        bail!(
+            "Downtime(s) {} trigger other downtimes, which would never be triggered after the old downtime is removed. Remove the downtimes and schedule them again instead",
+            triggers.join(", ")
+        );
+    }
+
+    let now = chrono::Local::now().timestamp();
+    let mut names = Vec::new();
+    let mut payloads = Vec::new();
+
+    for d in &downtimes.results {
+        let start = d.attrs.start_time.round() as i64;
+        let old_end = d.attrs.end_time.round() as i64;
+
+        // The duration of fixed downtimes is the downtime window
+        let new_end = match (end, extend, duration) {
+            (Some(e), _, _) => Some(e),
+            (None, Some(x), _) => old_end.checked_add(x),
+            (None, None, Some(v)) if d.attrs.fixed => start.checked_add(v),
+            _ => Some(old_end),
+        };
+        let new_end = match new_end {
+            Some(v) => v,
+            None => bail!(
+                "Can't modify downtime {}: new end time is too large",
+                d.name
+            ),
+        };
+
+        let (start, new_end) =
+            match command::add_downtime::downtime_window(Some(start), Some(new_end), -1, now) {
+                Ok(v) => v,
+                Err(e) => bail!("Can't modify downtime {}: {}", d.name, e),
+            };
+
+        let new_duration = if d.attrs.fixed {
+            None
+        } else {
+            match duration {
+                Some(v) => Some(v),
+                None => Some(d.attrs.duration.round() as i64),
+            }
+        };
+
+        // The new downtime is scheduled for exactly the object of the old downtime
+        let mut f = command::filter::Filter::new();
+        let var = f.add_var(
+            "host.name",
+            serde_json::Value::from(d.attrs.host_name.as_str()),
+        );
+        f.add(&format!("host.name == {}", var));
+        let obj_type = if d.attrs.service_name.is_empty() {
+            "Host"
+        } else {
+            let var = f.add_var(
+                "service.name",
+                serde_json::Value::from(d.attrs.service_name.as_str()),
+            );
+            f.add(&format!("service.name == {}", var));
+            "Service"
+        };
+
+        payloads.push(serde_json::to_string(&json_data::AddDowntimeRequest {
+            object_type: obj_type.to_string(),
+            filter: f.expression(),
+            filter_vars: f.vars(),
+            author: if author.is_empty() {
+                d.attrs.author.clone()
+            } else {
+                author.clone()
+            },
+            comment: d.attrs.comment.clone(),
+            start_time: start,
+            end_time: new_end,
+            fixed: d.attrs.fixed,
+            duration: new_duration,
+            all_services: false,
+            trigger_name: d.attrs.triggered_by.clone(),
+            child_options: "DowntimeNoChildren".to_string(),
+        })?);
+        names.push(d.name.clone());
+    }
+
+    if cfg.debug {
+        eprintln!("HTTP method: POST");
+        eprintln!("URL: {}{}", cfg.url, constants::ICINGA2_ADD_DOWNTIME);
+        for p in &payloads {
+            eprintln!("Payload: {}", p);
+        }
+    }
+
+    let mut selected = command::filter::Filter::new();
+    let var = selected.add_var("downtime.names", serde_json::Value::from(names.clone()));
+    selected.add(&format!("downtime.__name in {}", var));
+
+    if !command::objects::preflight_all(
+        cfg,
+        constants::ICINGA2_ADD_DOWNTIME,
+        "Downtime",
+        &selected,
+        &payloads,
+    )? {
+        return Ok(());
+    }
+
+    let mut failed = 0;
+    for (old, payload) in names.iter().zip(payloads) {
+        match replace(cfg, old, payload, &author) {
+            Ok(new) => println!("{}{} -> {}", util::instance_prefix(cfg), old, new),
+            Err(e) => {
+                println!("{}{}: {}", util::instance_prefix(cfg), old, e);
+                failed += 1;
+            }
+        };
+    }
+
+    if failed > 0 {
+        bail!(
+            "{} of {} downtime(s) could not be modified",
+            failed,
+            names.len()
+        );
+    }
+
+    Ok(())
+}
+
+// Names of the downtimes having child downtimes and names of the downtimes triggering other
+// downtimes
+fn dependent_downtimes(
+    cfg: &configuration::Configuration,
+    names: &[String],
+) -> Result<(Vec<String>, Vec<String>), Box<dyn Error>> {
+    let mut filter = command::filter::Filter::new();
+    let var = filter.add_var("downtime.names", serde_json::Value::from(names.to_vec()));
+    filter.add(&format!(
+        "(downtime.parent in {} || downtime.triggered_by in {})",
+        var, var
+    ));
+
+    let (filter, filter_vars) = filter.into_parts();
+    let payload = serde_json::to_string(&json_data::Icinga2ObjectQuery {
+        filter,
+        filter_vars,
+    })?;
+
+    let dependents: json_data::Icinga2ChildDowntimes = command::objects::get_objects(
+        cfg,
+        constants::ICINGA2_OBJ_DOWNTIME,
+        "attrs=parent&attrs=triggered_by",
+        payload,
+    )?;
+
+    let mut parents = Vec::new();
+    let mut triggers = Vec::new();
+    for d in dependents.results {
+        if names.contains(&d.attrs.parent) {
+            parents.push(d.attrs.parent);
+        }
+        if names.contains(&d.attrs.triggered_by) {
+            triggers.push(d.attrs.triggered_by);
+        }
+    }
+
+    parents.sort();
+    parents.dedup();
+    triggers.sort();
+    triggers.dedup();
+
+    Ok((parents, triggers))
+}
+
+// Schedule the new downtime first and remove the old downtime only if the new downtime was
+// scheduled, so the object is covered by a downtime all the time. Returns the name of the new
+// downtime.
+fn replace(
+    cfg: &configuration::Configuration,
+    old: &str,
+    payload: String,
+    author: &str,
+) -> Result<String, Box<dyn Error>> {
+    let added = command::add_downtime::send(cfg, payload)?;
+
+    let new = match added
+        .results
+        .into_iter()
+        .find(|r| r.code.round() as i64 == 200)
+    {
+        Some(r) => match r.name {
+            Some(v) => v,
+            None => bail!("No name received for the new downtime"),
+        },
+        None => bail!("New downtime was not scheduled, the old downtime is kept"),
+    };
+
+    let payload = serde_json::to_string(&json_data::DelDowntimeRequest {
+        downtime: old.to_string(),
+        author: author.to_string(),
+        object_type: String::new(),
+        filter: String::new(),
+        filter_vars: json_data::FilterVars::new(),
+    })?;
+
+    if cfg.debug {
+        eprintln!("HTTP method: POST");
+        eprintln!("URL: {}{}", cfg.url, constants::ICINGA2_DEL_DOWNTIME);
+        eprintln!("Payload: {}", payload);
+    }
+
+    let req = request::build_client(cfg, "")?
+        .post(
+            format!(
+                "{url}{path}",
+                url = cfg.url,
+                path = constants::ICINGA2_DEL_DOWNTIME,
+            )
+            .as_str(),
+        )
+        .body(payload)
+        .send()?;
+
+    if req.status() != StatusCode::OK {
+        let reason = req.status().canonical_reason().unwrap_or("None");
+        bail!(
+            "New downtime {} was scheduled, but the old downtime could not be removed: got \"{}\" instead of \"200 OK\"",
+            new,
+            reason
+        );
+    }
+
+    Ok(new)
+}
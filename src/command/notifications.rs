@@ -0,0 +1,114 @@
+use crate::command;
+use crate::configuration;
+use crate::json_data;
+use crate::usage;
+use crate::util;
+
+use std::collections::{BTreeMap, HashMap};
+use std::error::Error;
+
+pub fn enable(
+    cfg: &configuration::Configuration,
+    opt: &clap::ArgMatches,
+) -> Result<(), Box<dyn Error>> {
+    if opt.is_present("help") {
+        usage::version::show();
+        usage::enable_notifications::show();
+        return Ok(());
+    };
+
+    set_notifications(cfg, opt, true)
+}
+
+pub fn disable(
+    cfg: &configuration::Configuration,
+    opt: &clap::ArgMatches,
+) -> Result<(), Box<dyn Error>> {
+    if opt.is_present("help") {
+        usage::version::show();
+        usage::disable_notifications::show();
+        return Ok(());
+    };
+
+    set_notifications(cfg, opt, false)
+}
+
+// Set the enable_notifications attribute of the selected hosts or services
+fn set_notifications(
+    cfg: &configuration::Configuration,
+    opt: &clap::ArgMatches,
+    enable: bool,
+) -> Result<(), Box<dyn Error>> {
+    let mut state_map = HashMap::new();
+
+    let selector = command::filter::ObjectSelector::from_options(opt);
+
+    if opt.is_present("warning") {
+        state_map.insert("==1".to_string(), String::new());
+    }
+    if opt.is_present("critical") {
+        state_map.insert("==2".to_string(), String::new());
+    }
+    if opt.is_present("unknown") {
+        state_map.insert("==3".to_string(), String::new());
+    }
+
+    if selector.is_empty() {
+        bail!("Provide a host, service, group and/or filter to change notifications for");
+    }
+
+    let (obj_type, mut filter) = command::filter::build_object_filter(&selector);
+    if !state_map.is_empty() {
+        filter.add(&command::filter::build_state_filter(
+            &obj_type.to_lowercase(),
+            &state_map,
+        ));
+    }
+
+    let mut attrs = BTreeMap::new();
+    attrs.insert(
+        "enable_notifications".to_string(),
+        serde_json::Value::from(enable),
+    );
+
+    let payload = serde_json::to_string(&json_data::ModifyObjectRequest {
+        filter: filter.expression(),
+        filter_vars: filter.vars(),
+        attrs,
+        restore_attrs: Vec::new(),
+    })?;
+
+    if !command::objects::preflight(
+        cfg,
+        command::objects::modify_path(obj_type)?,
+        obj_type,
+        &filter,
+        &payload,
+    )? {
+        return Ok(());
+    }
+
+    let result = command::objects::modify(cfg, obj_type, payload)?;
+
+    let state = if enable { "enabled" } else { "disabled" };
+    for r in result.results {
+        match r.name {
+            Some(v) if r.code.round() as i64 == 200 => {
+                println!(
+                    "{}{}: notifications {}",
+                    util::instance_prefix(cfg),
+                    v,
+                    state
+                );
+            }
+            Some(v) => {
+                println!("{}{}: {}", util::instance_prefix(cfg), v, r.status);
+            }
+            None => {
+                println!("{}{}", util::instance_prefix(cfg), r.status);
+            }
+        };
+    }
+
+    Ok(())
+}
@@ -0,0 +1,232 @@
+use crate::command;
+use crate::configuration;
+use crate::constants;
+use crate::json_data;
+use crate::request;
+use crate::util;
+
+use http::StatusCode;
+use serde::de::DeserializeOwned;
+use std::error::Error;
+use std::io::IsTerminal;
+
+// Query objects from the Icinga2 API, the payload contains the filter of the query
+pub fn get_objects<T: DeserializeOwned>(
+    cfg: &configuration::Configuration,
+    path: &str,
+    attrs: &str,
+    payload: String,
+) -> Result<T, Box<dyn Error>> {
+    let url = if attrs.is_empty() {
+        format!("{}{}", cfg.url, path)
+    } else {
+        format!("{}{}?{}", cfg.url, path, attrs)
+    };
+
+    if cfg.debug {
+        eprintln!("HTTP method: GET");
+        eprintln!("URL: {}", url);
+        eprintln!("Payload: {}", payload);
+    }
+
+    let req = request::build_client(cfg, "GET")?
+        .post(url.as_str())
+        .body(payload)
+        .send()?;
+
+    if req.status() != StatusCode::OK {
+        let reason = req.status().canonical_reason().unwrap_or("None");
+        bail!(
+            "Invalid status code received, exepected \"200 OK\", got \"{}\" instead",
+            reason
+        );
+    }
+
+    let raw = req.text()?;
+
+    match serde_json::from_str(raw.as_str()) {
+        Ok(v) => Ok(v),
+        Err(e) => Err(Box::new(e)),
+    }
+}
+
+// Names of the host (Host), service (Service) or downtime (Downtime) objects matching the filter
+// of an action
+pub fn resolve(
+    cfg: &configuration::Configuration,
+    obj_type: &str,
+    filter: &command::filter::Filter,
+) -> Result<Vec<String>, Box<dyn Error>> {
+    let path = match obj_type {
+        "Host" => constants::ICINGA2_OBJ_HOST,
+        "Service" => constants::ICINGA2_OBJ_SERVICE,
+        "Downtime" => constants::ICINGA2_OBJ_DOWNTIME,
+        _ => {
+            bail!("Can't resolve objects of type {}", obj_type);
+        }
+    };
+
+    let payload = serde_json::to_string(&json_data::Icinga2ObjectQuery {
+        filter: filter.expression(),
+        filter_vars: filter.vars(),
+    })?;
+
+    let objects: json_data::Icinga2Objects = get_objects(cfg, path, "attrs=name", payload)?;
+
+    let mut names: Vec<String> = objects.results.into_iter().map(|o| o.name).collect();
+    names.sort();
+
+    Ok(names)
+}
+
+// Path to modify the attributes of host (Host) or service (Service) objects
+pub fn modify_path(obj_type: &str) -> Result<&'static str, Box<dyn Error>> {
+    match obj_type {
+        "Host" => Ok(constants::ICINGA2_OBJ_HOST),
+        "Service" => Ok(constants::ICINGA2_OBJ_SERVICE),
+        _ => bail!("Can't modify objects of type {}", obj_type),
+    }
+}
+
+// Modify the attributes of the host or service objects selected by the filter of the payload.
+// Returns the modified objects.
+pub fn modify(
+    cfg: &configuration::Configuration,
+    obj_type: &str,
+    payload: String,
+) -> Result<json_data::Icinga2ActionReply, Box<dyn Error>> {
+    let path = modify_path(obj_type)?;
+
+    if cfg.debug {
+        eprintln!("HTTP method: POST");
+        eprintln!("URL: {}{}", cfg.url, path);
+        eprintln!("Payload: {}", payload);
+    }
+
+    let req = request::build_client(cfg, "")?
+        .post(format!("{}{}", cfg.url, path).as_str())
+        .body(payload)
+        .send()?;
+
+    if req.status() == StatusCode::NOT_FOUND {
+        bail!("No matching objects found");
+    }
+
+    // Like actions, a HTTP 500 is returned if the attributes of some objects can't be modified
+    if req.status() != StatusCode::OK && req.status() != StatusCode::INTERNAL_SERVER_ERROR {
+        let reason = req.status().canonical_reason().unwrap_or("None");
+        bail!(
+            "Invalid status code received, exepected HTTP status code 200 or 500, got \"{}\" instead",
+            reason
+        );
+    }
+
+    let raw = req.text()?;
+    Ok(serde_json::from_str(&raw)?)
+}
+
+// Must be called before an action is sent to the Icinga2 API. Returns false if the action must
+// not be run.
+// In dry-run mode the objects matching the filter of the action are resolved and printed together
+// with the payload of the action. Actions on objects selected by name (empty object type) only
+// show the payload.
+// Otherwise the action must be confirmed if it affects more than confirm_threshold objects and
+// it is refused if it affects more than max_objects objects, unless --force-many is used. A
+// declined confirmation is an error.
+pub fn preflight(
+    cfg: &configuration::Configuration,
+    action: &str,
+    obj_type: &str,
+    filter: &command::filter::Filter,
+    payload: &str,
+) -> Result<bool, Box<dyn Error>> {
+    preflight_all(cfg, action, obj_type, filter, &[payload.to_string()])
+}
+
+// Same as preflight for an action sent multiple times with different payloads but the same filter
+pub fn preflight_all(
+    cfg: &configuration::Configuration,
+    action: &str,
+    obj_type: &str,
+    filter: &command::filter::Filter,
+    payloads: &[String],
+) -> Result<bool, Box<dyn Error>> {
+    let prefix = util::instance_prefix(cfg);
+
+    if cfg.dry_run {
+        println!("{}Dry run, not sending POST {}", prefix, action);
+        for payload in payloads {
+            println!("{}Payload: {}", prefix, payload);
+        }
+
+        if !obj_type.is_empty() {
+            let names = resolve(cfg, obj_type, filter)?;
+            println!(
+                "{}{} {} object(s) would be affected:",
+                prefix,
+                names.len(),
+                obj_type
+            );
+            for n in names {
+                println!("{}    {}", prefix, n);
+            }
+        }
+
+        return Ok(false);
+    }
+
+    if obj_type.is_empty() || cfg.force_many || (cfg.confirm_threshold == 0 && cfg.max_objects == 0)
+    {
+        return Ok(true);
+    }
+
+    let count = resolve(cfg, obj_type, filter)?.len() as u64;
+    check_limits(cfg, action, obj_type, count)?;
+
+    Ok(true)
+}
+
+// Apply max_objects and confirm_threshold to an action affecting count objects, see preflight
+pub fn check_limits(
+    cfg: &configuration::Configuration,
+    action: &str,
+    obj_type: &str,
+    count: u64,
+) -> Result<(), Box<dyn Error>> {
+    let prefix = util::instance_prefix(cfg);
+
+    if cfg.force_many {
+        return Ok(());
+    }
+
+    if cfg.max_objects > 0 && count > cfg.max_objects {
+        bail!(
+            "Action would affect {} {} objects, but at most {} objects are allowed (max_objects). Use --force-many to run the action anyway",
+            count,
+            obj_type,
+            cfg.max_objects
+        );
+    }
+
+    if cfg.confirm_threshold > 0 && count > cfg.confirm_threshold {
+        let question = format!(
+            "{}Action {} would affect {} {} objects. Continue?",
+            prefix, action, count, obj_type
+        );
+
+        // Without a terminal there is nobody to answer, e.g. if run by cron
+        if !std::io::stdin().is_terminal() {
+            bail!(
+                "Action would affect {} {} objects and confirmation is required, but standard input is not a terminal. Use --force-many to run the action anyway",
+                count,
+                obj_type
+            );
+        }
+
+        if !util::confirm(&question)? {
+            bail!("Aborted by user");
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,855 @@
+use crate::command;
+use crate::configuration;
+use crate::constants;
+use crate::json_data;
+use crate::perfdata;
+use crate::request;
+use crate::time_parser;
+use crate::usage;
+use crate::util;
+
+use http::StatusCode;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs;
+use std::io::Read;
+use std::str::FromStr;
+use std::sync::{Condvar, Mutex};
+use std::thread;
+
+// Number of concurrent requests for check results read from a file
+const DEFAULT_PARALLEL: usize = 4;
+
+// Maximal number of hosts in a single request
+const MAX_GROUP_SIZE: usize = 100;
+
+// Standard input is read only once and shared by all instances
+static STDIN: Mutex<Option<String>> = Mutex::new(None);
+
+// Passive check result for a host or a service (if service is not empty)
+#[derive(Clone)]
+pub struct CheckResult {
+    pub host: String,
+    pub service: String,
+    pub exit_status: i32,
+    pub output: String,
+    pub perfdata: Vec<String>,
+    pub check_source: String,
+    pub ttl: Option<i64>,
+    pub execution_start: Option<i64>,
+    pub execution_end: Option<i64>,
+}
+
+// Check results with the same key only differ in the host and can be submitted together
+#[derive(Hash, PartialEq, Eq)]
+struct GroupKey {
+    service: String,
+    exit_status: i32,
+    output: String,
+    perfdata: Vec<String>,
+    check_source: String,
+    ttl: Option<i64>,
+    execution_start: Option<i64>,
+    execution_end: Option<i64>,
+}
+
+impl CheckResult {
+    // Object type, filter and payload of the process-check-result action. The object is
+    // selected by its exact name.
+    pub fn request(
+        &self,
+    ) -> Result<(&'static str, command::filter::Filter, String), Box<dyn Error>> {
+        self.request_for(std::slice::from_ref(&self.host))
+    }
+
+    // Same as request, but the check result is submitted for the host (or the service) of
+    // all hosts
+    pub fn request_for(
+        &self,
+        hosts: &[String],
+    ) -> Result<(&'static str, command::filter::Filter, String), Box<dyn Error>> {
+        let mut filter = command::filter::Filter::new();
+
+        if hosts.len() == 1 {
+            let var = filter.add_var("host.name", serde_json::Value::from(hosts[0].as_str()));
+            filter.add(&format!("host.name == {}", var));
+        } else {
+            let var = filter.add_var("host.names", serde_json::Value::from(hosts.to_vec()));
+            filter.add(&format!("host.name in {}", var));
+        }
+
+        let obj_type = if self.service.is_empty() {
+            "Host"
+        } else {
+            let var = filter.add_var(
+                "service.name",
+                serde_json::Value::from(self.service.as_str()),
+            );
+            filter.add(&format!("service.name == {}", var));
+            "Service"
+        };
+
+        let payload = serde_json::to_string(&json_data::ProcessCheckResultRequest {
+            object_type: obj_type.to_string(),
+            filter: filter.expression(),
+            filter_vars: filter.vars(),
+            exit_status: self.exit_status,
+            plugin_output: self.output.clone(),
+            performance_data: self.perfdata.clone(),
+            check_source: self.check_source.clone(),
+            ttl: self.ttl,
+            execution_start: self.execution_start,
+            execution_end: self.execution_end,
+        })?;
+
+        Ok((obj_type, filter, payload))
+    }
+
+    pub fn validate(&self) -> Result<(), Box<dyn Error>> {
+        if let Some(ttl) = self.ttl {
+            if ttl <= 0 {
+                bail!("TTL must be greater than 0");
+            }
+        }
+
+        if let (Some(start), Some(end)) = (self.execution_start, self.execution_end) {
+            if end < start {
+                bail!("Execution end must not be before execution start");
+            }
+        }
+
+        Ok(())
+    }
+
+    // Name of the host or service object
+    fn object(&self) -> String {
+        if self.service.is_empty() {
+            self.host.clone()
+        } else {
+            format!("{}!{}", self.host, self.service)
+        }
+    }
+
+    fn group_key(&self) -> GroupKey {
+        GroupKey {
+            service: self.service.clone(),
+            exit_status: self.exit_status,
+            output: self.output.clone(),
+            perfdata: self.perfdata.clone(),
+            check_source: self.check_source.clone(),
+            ttl: self.ttl,
+            execution_start: self.execution_start,
+            execution_end: self.execution_end,
+        }
+    }
+}
+
+pub fn run(
+    cfg: &configuration::Configuration,
+    opt: &clap::ArgMatches,
+) -> Result<(), Box<dyn Error>> {
+    if opt.is_present("help") {
+        usage::version::show();
+        usage::process_check_result::show();
+        return Ok(());
+    }
+
+    if let Some(file) = opt.value_of("from_file") {
+        if [
+            "host_object",
+            "service_object",
+            "exit_status",
+            "output",
+            "perfdata",
+        ]
+        .iter()
+        .any(|o| opt.is_present(o))
+        {
+            bail!("Check results from a file and from the command line are mutually exclusive");
+        }
+
+        let parallel = match opt.value_of("parallel") {
+            Some(v) => match usize::from_str(v) {
+                Ok(0) => bail!("Number of parallel requests must be greater than 0"),
+                Ok(n) => n,
+                Err(e) => bail!("Can't convert {} into a number: {}", v, e),
+            },
+            None => DEFAULT_PARALLEL,
+        };
+
+        return run_bulk(cfg, file, parallel);
+    }
+
+    let host = match opt.value_of("host_object") {
+        Some(v) => v.to_string(),
+        None => bail!("Host is mandatory"),
+    };
+
+    let service = match opt.value_of("service_object") {
+        Some(v) => v.to_string(),
+        None => String::new(),
+    };
+
+    let exit_status = match opt.value_of("exit_status") {
+        Some(v) => parse_exit_status(v, service.is_empty())?,
+        None => bail!("Exit status is mandatory"),
+    };
+
+    let (output, mut perf) = match opt.value_of("output") {
+        Some(v) => split_output(v)?,
+        None => bail!("Plugin output is mandatory"),
+    };
+
+    if let Some(values) = opt.values_of("perfdata") {
+        for v in values {
+            perf.extend(perfdata::parse_perfdata(v)?);
+        }
+    }
+
+    let check_source = match opt.value_of("check_source") {
+        Some(v) => v.to_string(),
+        None => String::new(),
+    };
+
+    let ttl = match opt.value_of("ttl") {
+        Some(v) => match time_parser::parse_duration(v) {
+            Ok(d) => Some(d),
+            Err(e) => bail!("Can't parse TTL: {}", e),
+        },
+        None => None,
+    };
+
+    let execution_start = match opt.value_of("execution_start") {
+        Some(v) => match time_parser::parse_time(v) {
+            Ok(t) => Some(t),
+            Err(e) => bail!("Can't parse execution start: {}", e),
+        },
+        None => None,
+    };
+
+    let execution_end = match opt.value_of("execution_end") {
+        Some(v) => match time_parser::parse_time(v) {
+            Ok(t) => Some(t),
+            Err(e) => bail!("Can't parse execution end: {}", e),
+        },
+        None => None,
+    };
+
+    let result = CheckResult {
+        host,
+        service,
+        exit_status,
+        output,
+        perfdata: perf,
+        check_source,
+        ttl,
+        execution_start,
+        execution_end,
+    };
+    result.validate()?;
+
+    let (obj_type, filter, payload) = result.request()?;
+
+    if cfg.debug {
+        eprintln!("HTTP method: POST");
+        eprintln!(
+            "URL: {}{}",
+            cfg.url,
+            constants::ICINGA2_PROCESS_CHECK_RESULT
+        );
+        eprintln!("Payload: {}", payload);
+    }
+
+    if !command::objects::preflight(
+        cfg,
+        constants::ICINGA2_PROCESS_CHECK_RESULT,
+        obj_type,
+        &filter,
+        &payload,
+    )? {
+        return Ok(());
+    }
+
+    let action_result = submit(cfg, payload)?;
+
+    for ar in action_result.results {
+        println!("{}{}", util::instance_prefix(cfg), ar.status);
+    }
+
+    Ok(())
+}
+
+// Send the process-check-result action and return the reply
+pub fn submit(
+    cfg: &configuration::Configuration,
+    payload: String,
+) -> Result<json_data::Icinga2ActionReply, Box<dyn Error>> {
+    let req = request::build_client(cfg, "")?
+        .post(
+            format!(
+                "{url}{path}",
+                url = cfg.url,
+                path = constants::ICINGA2_PROCESS_CHECK_RESULT,
+            )
+            .as_str(),
+        )
+        .body(payload)
+        .send()?;
+
+    match req.status() {
+        StatusCode::OK => {}
+        StatusCode::NOT_FOUND => {
+            bail!("Object not found");
+        }
+        _ => {
+            let reason = req.status().canonical_reason().unwrap_or("None");
+            bail!(
+                "Invalid status code received, exepected HTTP status code 200, got \"{}\" instead",
+                reason
+            );
+        }
+    };
+
+    let raw = req.text()?;
+
+    match serde_json::from_str(&raw) {
+        Ok(v) => Ok(v),
+        Err(e) => Err(Box::new(e)),
+    }
+}
+
+// Exit status as number or name. Hosts only know UP (0) and DOWN (1).
+pub fn parse_exit_status(s: &str, host: bool) -> Result<i32, Box<dyn Error>> {
+    let status = s.to_lowercase();
+
+    if host {
+        return match status.as_str() {
+            "0" | "up" => Ok(0),
+            "1" | "down" => Ok(1),
+            _ => bail!(
+                "Invalid exit status {} for a host, only 0 (up) and 1 (down) are allowed",
+                s
+            ),
+        };
+    }
+
+    match status.as_str() {
+        "0" | "ok" => Ok(constants::PLUGIN_OK),
+        "1" | "warning" => Ok(constants::PLUGIN_WARNING),
+        "2" | "critical" => Ok(constants::PLUGIN_CRITICAL),
+        "3" | "unknown" => Ok(constants::PLUGIN_UNKNOWN),
+        _ => bail!("Invalid exit status {} for a service, only 0 (ok), 1 (warning), 2 (critical) and 3 (unknown) are allowed", s),
+    }
+}
+
+// Like the output of a plugin, performance data can follow a | in the first line of the output.
+// Returns the output without performance data and the parsed performance data.
+pub fn split_output(s: &str) -> Result<(String, Vec<String>), Box<dyn Error>> {
+    let first_line_end = s.find('\n').unwrap_or(s.len());
+
+    match s[..first_line_end].find('|') {
+        Some(i) => Ok((
+            format!("{}{}", s[..i].trim_end(), &s[first_line_end..]),
+            perfdata::parse_perfdata(&s[i + 1..first_line_end])?,
+        )),
+        None => Ok((s.to_string(), Vec::new())),
+    }
+}
+
+// Line number, object name and result of a check result read from a file
+type RecordResult = (usize, String, Result<(), String>);
+
+// Submit check results read from a JSON lines file (- for standard input). Check results only
+// differing in the host are submitted in a single request, up to parallel requests are sent
+// concurrently.
+fn run_bulk(
+    cfg: &configuration::Configuration,
+    file: &str,
+    parallel: usize,
+) -> Result<(), Box<dyn Error>> {
+    let content = read_input(file)?;
+
+    let mut results: Vec<RecordResult> = Vec::new();
+    let mut records = Vec::new();
+
+    for (i, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        match parse_record(line) {
+            Ok(r) => records.push((i + 1, r)),
+            Err(e) => results.push((i + 1, String::new(), Err(e.to_string()))),
+        };
+    }
+
+    let total = results.len() + records.len();
+    let groups = group_records(records);
+
+    let mut payloads = Vec::new();
+    for g in &groups {
+        let hosts: Vec<String> = g.iter().map(|(_, r)| r.host.clone()).collect();
+        let (_, _, payload) = g[0].1.request_for(&hosts)?;
+        payloads.push(payload);
+    }
+
+    if cfg.debug {
+        eprintln!("HTTP method: POST");
+        eprintln!(
+            "URL: {}{}",
+            cfg.url,
+            constants::ICINGA2_PROCESS_CHECK_RESULT
+        );
+        for p in &payloads {
+            eprintln!("Payload: {}", p);
+        }
+    }
+
+    // Every check result is submitted for a single object selected by its name
+    if command::objects::preflight_all(
+        cfg,
+        constants::ICINGA2_PROCESS_CHECK_RESULT,
+        "",
+        &command::filter::Filter::new(),
+        &payloads,
+    )? {
+        let mut objects: Vec<String> = groups
+            .iter()
+            .flat_map(|g| g.iter().map(|(_, r)| r.object()))
+            .collect();
+        objects.sort();
+        objects.dedup();
+        command::objects::check_limits(
+            cfg,
+            constants::ICINGA2_PROCESS_CHECK_RESULT,
+            "Host/Service",
+            objects.len() as u64,
+        )?;
+
+        // A group is only started if all groups it depends on are done, state contains the
+        // started and the finished groups
+        let deps = group_dependencies(&groups);
+        let state = Mutex::new((vec![false; groups.len()], vec![false; groups.len()]));
+        let finished = Condvar::new();
+        let submitted = Mutex::new(Vec::new());
+
+        thread::scope(|s| {
+            for _ in 0..parallel {
+                s.spawn(|| loop {
+                    let i = {
+                        let mut st = state.lock().unwrap();
+                        loop {
+                            let (started, done) = &mut *st;
+                            if started.iter().all(|v| *v) {
+                                return;
+                            }
+
+                            match (0..started.len())
+                                .find(|&i| !started[i] && deps[i].iter().all(|&d| done[d]))
+                            {
+                                Some(i) => {
+                                    started[i] = true;
+                                    break i;
+                                }
+                                None => st = finished.wait(st).unwrap(),
+                            };
+                        }
+                    };
+
+                    let r = submit_group(cfg, &groups[i]);
+                    submitted.lock().unwrap().extend(r);
+
+                    state.lock().unwrap().1[i] = true;
+                    finished.notify_all();
+                });
+            }
+        });
+
+        results.extend(submitted.into_inner().unwrap());
+    }
+
+    results.sort_by_key(|r| r.0);
+
+    let prefix = util::instance_prefix(cfg);
+    let mut failed = 0;
+    for (line, object, result) in &results {
+        match result {
+            Ok(_) => {
+                println!("{}line {}: {}: OK", prefix, line, object);
+            }
+            Err(e) => {
+                failed += 1;
+                if object.is_empty() {
+                    println!("{}line {}: {}", prefix, line, e);
+                } else {
+                    println!("{}line {}: {}: {}", prefix, line, object, e);
+                }
+            }
+        };
+    }
+
+    if !cfg.dry_run {
+        println!(
+            "{}Submitted {} of {} check result(s)",
+            prefix,
+            results.len() - failed,
+            results.len()
+        );
+    }
+
+    if failed > 0 {
+        bail!("{} of {} check result(s) failed", failed, total);
+    }
+
+    Ok(())
+}
+
+fn read_input(file: &str) -> Result<String, Box<dyn Error>> {
+    if file != "-" {
+        return match fs::read_to_string(file) {
+            Ok(v) => Ok(v),
+            Err(e) => bail!("Can't read {}: {}", file, e),
+        };
+    }
+
+    let mut cached = STDIN.lock().unwrap();
+    if let Some(v) = cached.as_ref() {
+        return Ok(v.clone());
+    }
+
+    let mut content = String::new();
+    std::io::stdin().read_to_string(&mut content)?;
+    *cached = Some(content.clone());
+
+    Ok(content)
+}
+
+// A check result is a JSON object like
+//   {"host": "web1", "service": "http", "exit_status": 2, "output": "...", "perfdata": "time=1s"}
+fn parse_record(line: &str) -> Result<CheckResult, Box<dyn Error>> {
+    let record: json_data::CheckResultRecord = match serde_json::from_str(line) {
+        Ok(v) => v,
+        Err(e) => bail!("Invalid check result: {}", e),
+    };
+
+    if record.host.is_empty() {
+        bail!("Host is mandatory");
+    }
+
+    let exit_status = match &record.exit_status {
+        serde_json::Value::Number(n) => {
+            parse_exit_status(&n.to_string(), record.service.is_empty())?
+        }
+        serde_json::Value::String(s) => parse_exit_status(s, record.service.is_empty())?,
+        v => bail!("Invalid exit status {}", v),
+    };
+
+    let (output, mut perf) = split_output(&record.output)?;
+
+    match &record.perfdata {
+        serde_json::Value::Null => {}
+        serde_json::Value::String(s) => perf.extend(perfdata::parse_perfdata(s)?),
+        serde_json::Value::Array(values) => {
+            for v in values {
+                match v.as_str() {
+                    Some(s) => perf.extend(perfdata::parse_perfdata(s)?),
+                    None => bail!("Invalid performance data {}", v),
+                };
+            }
+        }
+        v => bail!("Invalid performance data {}", v),
+    };
+
+    let result = CheckResult {
+        host: record.host,
+        service: record.service,
+        exit_status,
+        output,
+        perfdata: perf,
+        check_source: record.check_source,
+        ttl: record.ttl,
+        execution_start: record.execution_start,
+        execution_end: record.execution_end,
+    };
+    result.validate()?;
+
+    Ok(result)
+}
+
+// Group check results which only differ in the host. The check results of an object must be
+// processed in the order of the file, so a check result is only added to a group after the
+// last group containing the object.
+fn group_records(records: Vec<(usize, CheckResult)>) -> Vec<Vec<(usize, CheckResult)>> {
+    let mut groups: Vec<Vec<(usize, CheckResult)>> = Vec::new();
+    let mut index: HashMap<GroupKey, usize> = HashMap::new();
+    let mut last: HashMap<String, usize> = HashMap::new();
+
+    for (line, r) in records {
+        let key = r.group_key();
+        let object = r.object();
+        let after = last.get(&object).copied();
+
+        let i = match index.get(&key) {
+            Some(&i) if groups[i].len() < MAX_GROUP_SIZE && after.is_none_or(|a| i > a) => {
+                groups[i].push((line, r));
+                i
+            }
+            _ => {
+                index.insert(key, groups.len());
+                groups.push(vec![(line, r)]);
+                groups.len() - 1
+            }
+        };
+
+        last.insert(object, i);
+    }
+
+    groups
+}
+
+// Earlier groups containing an object of a group. A group must not be submitted before these
+// groups are done.
+fn group_dependencies(groups: &[Vec<(usize, CheckResult)>]) -> Vec<Vec<usize>> {
+    let mut last: HashMap<String, usize> = HashMap::new();
+
+    groups
+        .iter()
+        .enumerate()
+        .map(|(i, g)| {
+            let mut deps = Vec::new();
+            for (_, r) in g {
+                if let Some(d) = last.insert(r.object(), i) {
+                    deps.push(d);
+                }
+            }
+            deps.sort_unstable();
+            deps.dedup();
+            deps
+        })
+        .collect()
+}
+
+fn submit_group(
+    cfg: &configuration::Configuration,
+    group: &[(usize, CheckResult)],
+) -> Vec<RecordResult> {
+    let fail = |e: String| -> Vec<RecordResult> {
+        group
+            .iter()
+            .map(|(l, r)| (*l, r.object(), Err(e.clone())))
+            .collect()
+    };
+
+    let hosts: Vec<String> = group.iter().map(|(_, r)| r.host.clone()).collect();
+    let (obj_type, filter, payload) = match group[0].1.request_for(&hosts) {
+        Ok(v) => v,
+        Err(e) => return fail(e.to_string()),
+    };
+
+    let reply = match submit(cfg, payload) {
+        Ok(v) => v,
+        Err(e) => return fail(e.to_string()),
+    };
+
+    // The check results of a group are the same, so an error applies to all of them
+    if let Some(r) = reply.results.iter().find(|r| r.code.round() as i64 != 200) {
+        return fail(r.status.clone());
+    }
+
+    if reply.results.len() == group.len() {
+        return group
+            .iter()
+            .map(|(l, r)| (*l, r.object(), Ok(())))
+            .collect();
+    }
+
+    // Some objects don't exist
+    let existing = match command::objects::resolve(cfg, obj_type, &filter) {
+        Ok(v) => v,
+        Err(e) => return fail(e.to_string()),
+    };
+
+    group
+        .iter()
+        .map(|(l, r)| {
+            let object = r.object();
+            if existing.contains(&object) {
+                (*l, object, Ok(()))
+            } else {
+                (*l, object, Err("Object not found".to_string()))
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exit_status() {
+        assert_eq!(parse_exit_status("0", false).unwrap(), 0);
+        assert_eq!(parse_exit_status("Critical", false).unwrap(), 2);
+        assert_eq!(parse_exit_status("3", false).unwrap(), 3);
+        assert!(parse_exit_status("4", false).is_err());
+        assert!(parse_exit_status("up", false).is_err());
+
+        assert_eq!(parse_exit_status("1", true).unwrap(), 1);
+        assert_eq!(parse_exit_status("DOWN", true).unwrap(), 1);
+        assert!(parse_exit_status("2", true).is_err());
+        assert!(parse_exit_status("warning", true).is_err());
+        assert!(parse_exit_status("x", true).is_err());
+    }
+
+    #[test]
+    fn output_with_perfdata() {
+        assert_eq!(
+            split_output("OK - 5 users | users=5;10;20").unwrap(),
+            (
+                "OK - 5 users".to_string(),
+                vec!["users=5;10;20".to_string()]
+            )
+        );
+        assert_eq!(
+            split_output("OK\nline 2 | no perfdata").unwrap(),
+            ("OK\nline 2 | no perfdata".to_string(), Vec::new())
+        );
+        assert_eq!(
+            split_output("OK | a=1 b=2\nline 2").unwrap(),
+            (
+                "OK\nline 2".to_string(),
+                vec!["a=1".to_string(), "b=2".to_string()]
+            )
+        );
+        assert!(split_output("OK | a=x").is_err());
+    }
+
+    #[test]
+    fn request_payload() {
+        let result = CheckResult {
+            host: "web\"1".to_string(),
+            service: "http".to_string(),
+            exit_status: 2,
+            output: "CRITICAL".to_string(),
+            perfdata: vec!["time=1s".to_string()],
+            check_source: String::new(),
+            ttl: Some(300),
+            execution_start: None,
+            execution_end: None,
+        };
+
+        let (obj_type, filter, payload) = result.request().unwrap();
+        assert_eq!(obj_type, "Service");
+        assert_eq!(
+            filter.expression(),
+            "host.name == icinga2ctl_host_name && service.name == icinga2ctl_service_name"
+        );
+
+        let parsed: serde_json::Value = serde_json::from_str(&payload).unwrap();
+        assert_eq!(parsed["filter_vars"]["icinga2ctl_host_name"], "web\"1");
+        assert_eq!(parsed["exit_status"], 2);
+        assert_eq!(parsed["performance_data"][0], "time=1s");
+        assert_eq!(parsed["ttl"], 300);
+        assert!(parsed.get("check_source").is_none());
+
+        let (_, filter, payload) = result
+            .request_for(&["a".to_string(), "b".to_string()])
+            .unwrap();
+        assert_eq!(
+            filter.expression(),
+            "host.name in icinga2ctl_host_names && service.name == icinga2ctl_service_name"
+        );
+        let parsed: serde_json::Value = serde_json::from_str(&payload).unwrap();
+        assert_eq!(
+            parsed["filter_vars"]["icinga2ctl_host_names"],
+            serde_json::json!(["a", "b"])
+        );
+    }
+
+    #[test]
+    fn records() {
+        let r = parse_record(
+            r#"{"host": "h1", "service": "s", "exit_status": "warning", "output": "WARN | a=1", "perfdata": ["b=2 c=3"], "ttl": 60}"#,
+        )
+        .unwrap();
+        assert_eq!(r.object(), "h1!s");
+        assert_eq!(r.exit_status, 1);
+        assert_eq!(r.output, "WARN");
+        assert_eq!(r.perfdata, vec!["a=1", "b=2", "c=3"]);
+        assert_eq!(r.ttl, Some(60));
+
+        let r = parse_record(r#"{"host": "h1", "exit_status": 1, "output": "DOWN"}"#).unwrap();
+        assert_eq!(r.object(), "h1");
+        assert_eq!(r.exit_status, 1);
+
+        for line in &[
+            "not json",
+            r#"{"host": "h1", "output": "x"}"#,
+            r#"{"host": "", "exit_status": 0, "output": "x"}"#,
+            r#"{"host": "h1", "exit_status": 2, "output": "x"}"#,
+            r#"{"host": "h1", "service": "s", "exit_status": 0, "output": "x", "perfdata": "a=b"}"#,
+            r#"{"host": "h1", "service": "s", "exit_status": 0, "output": "x", "perfdata": 1}"#,
+            r#"{"host": "h1", "service": "s", "exit_status": 0, "output": "x", "ttl": 0}"#,
+        ] {
+            assert!(parse_record(line).is_err(), "{} must be invalid", line);
+        }
+    }
+
+    #[test]
+    fn grouping() {
+        let line = |host: &str, status: i32| {
+            parse_record(&format!(
+                r#"{{"host": "{}", "service": "s", "exit_status": {}, "output": "x"}}"#,
+                host, status
+            ))
+            .unwrap()
+        };
+
+        let groups = group_records(vec![
+            (1, line("h1", 0)),
+            (2, line("h2", 0)),
+            (3, line("h3", 2)),
+            (4, line("h1", 0)),
+        ]);
+
+        let lines: Vec<Vec<usize>> = groups
+            .iter()
+            .map(|g| g.iter().map(|(l, _)| *l).collect())
+            .collect();
+        assert_eq!(lines, vec![vec![1, 2], vec![3], vec![4]]);
+        assert_eq!(group_dependencies(&groups), vec![vec![], vec![], vec![0]]);
+
+        // the CRITICAL result of h2 waits for the OK result of h2
+        let groups = group_records(vec![
+            (1, line("h2", 0)),
+            (2, line("h1", 0)),
+            (3, line("h1", 2)),
+            (4, line("h2", 2)),
+            (5, line("h3", 0)),
+        ]);
+
+        let lines: Vec<Vec<usize>> = groups
+            .iter()
+            .map(|g| g.iter().map(|(l, _)| *l).collect())
+            .collect();
+        assert_eq!(lines, vec![vec![1, 2, 5], vec![3, 4]]);
+        assert_eq!(group_dependencies(&groups), vec![vec![], vec![0]]);
+
+        // the OK result of h1 must not be merged into the first group, it would be submitted
+        // before the CRITICAL result
+        let groups = group_records(vec![
+            (1, line("h2", 0)),
+            (2, line("h1", 2)),
+            (3, line("h1", 0)),
+            (4, line("h3", 0)),
+        ]);
+
+        let lines: Vec<Vec<usize>> = groups
+            .iter()
+            .map(|g| g.iter().map(|(l, _)| *l).collect())
+            .collect();
+        assert_eq!(lines, vec![vec![1], vec![2], vec![3, 4]]);
+        assert_eq!(group_dependencies(&groups), vec![vec![], vec![], vec![1]]);
+    }
+}
@@ -3,9 +3,10 @@ use crate::configuration;
 use crate::constants;
 use crate::json_data;
 use crate::request;
+use crate::time_parser;
 use crate::usage;
+use crate::util;
 
-use chrono::DateTime;
 use http::StatusCode;
 use std::collections::HashMap;
 use std::error::Error;
@@ -14,13 +15,10 @@ pub fn run(
     cfg: &configuration::Configuration,
     opt: &clap::ArgMatches,
 ) -> Result<(), Box<dyn Error>> {
-    let mut filter = String::new();
     let mut force = true;
     let mut state_map = HashMap::new();
     let mut ack = -1;
-    let mut state_type = "";
-    let mut at_str = String::new();
-    let mut at_stamp_str = String::new();
+    let mut next_check = None;
 
     if opt.is_present("help") {
         usage::version::show();
@@ -62,73 +60,31 @@ pub fn run(
     };
 
     if !at.is_empty() {
-        match DateTime::parse_from_rfc3339(at.as_str()) {
-            Ok(v) => at_str = v.format("%s").to_string(),
-            Err(e) => bail!("Can't parse time as RFC3339 time: {}", e),
+        match time_parser::parse_time(&at) {
+            Ok(v) => next_check = Some(v),
+            Err(e) => bail!("Can't parse time: {}", e),
         };
     };
 
-    let hosts = match opt.value_of("host_object") {
-        Some(v) => v.to_string(),
-        None => String::new(),
-    };
-
-    let services = match opt.value_of("service_object") {
-        Some(v) => v.to_string(),
-        None => String::new(),
-    };
-
-    if hosts.is_empty() && services.is_empty() {
-        bail!("Provide a host and/or service for status display");
-    }
-
-    if !hosts.is_empty() && services.is_empty() {
-        // Show host status for hosts
-        let state_filter = command::filter::build_state_filter("host", &state_map);
-        let ack_filter = command::filter::build_ack_filter("host", ack);
-        state_type = "Host";
-
-        filter = format!(
-            "\"filter\":\"match(\\\"{}\\\", host.name) && {} && {}\"",
-            hosts, state_filter, ack_filter,
-        );
-    }
-
-    if hosts.is_empty() && !services.is_empty() {
-        // Show services for all hosts
-        let state_filter = command::filter::build_state_filter("service", &state_map);
-        let ack_filter = command::filter::build_ack_filter("service", ack);
-        state_type = "Service";
+    let selector = command::filter::ObjectSelector::from_options(opt);
 
-        filter = format!(
-            "\"filter\":\"match(\\\"{}\\\", service.name) && {} && {}\"",
-            services, state_filter, ack_filter,
-        );
+    if selector.is_empty() {
+        bail!("Provide a host, service, group and/or filter for status display");
     }
 
-    if !hosts.is_empty() && !services.is_empty() {
-        // Show services for hosts
-        let state_filter = command::filter::build_state_filter("service", &state_map);
-        let ack_filter = command::filter::build_ack_filter("service", ack);
-        state_type = "Service";
+    let (state_type, mut filter) = command::filter::build_object_filter(&selector);
+    let flt = state_type.to_lowercase();
 
-        filter = format!(
-            "\"filter\":\"match(\\\"{}\\\", host.name) && match(\\\"{}\\\", service.name) && {} && {}\"",
-            hosts, services, state_filter, ack_filter,
-        );
-    }
+    filter.add(&command::filter::build_state_filter(&flt, &state_map));
+    filter.add(&command::filter::build_ack_filter(&flt, ack));
 
-    if !at_str.is_empty() {
-        at_stamp_str = format!(",\"next_check\":\"{}\"", at_str);
-    };
-
-    let payload = format!(
-        "{{\"type\":\"{state_type}\",{filter},\"force\":{force}{at_str}}}",
-        state_type = state_type,
-        filter = filter,
-        force = force,
-        at_str = at_stamp_str,
-    );
+    let payload = serde_json::to_string(&json_data::RescheduleCheckRequest {
+        object_type: state_type.to_string(),
+        filter: filter.expression(),
+        filter_vars: filter.vars(),
+        force,
+        next_check,
+    })?;
 
     if cfg.debug {
         eprintln!("HTTP method: POST");
@@ -136,6 +92,16 @@ pub fn run(
         eprintln!("Payload: {}", payload);
     }
 
+    if !command::objects::preflight(
+        cfg,
+        constants::ICINGA2_RESCHEDULE_CHECK,
+        state_type,
+        &filter,
+        &payload,
+    )? {
+        return Ok(());
+    }
+
     let req = request::build_client(cfg, "")?
         .post(
             format!(
@@ -169,7 +135,7 @@ pub fn run(
     };
 
     for ar in action_result.results {
-        println!("{}", ar.status);
+        println!("{}{}", util::instance_prefix(cfg), ar.status);
     }
 
     Ok(())
@@ -0,0 +1,561 @@
+use crate::command;
+use crate::configuration;
+use crate::constants;
+use crate::json_data;
+use crate::time_parser;
+use crate::usage;
+use crate::util;
+
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, NaiveTime, Timelike, Weekday};
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::str::FromStr;
+
+// Upper limit of days searched for the next occurrences of the time ranges
+const MAX_DAYS: i64 = 3660;
+
+pub fn run(
+    cfg: &configuration::Configuration,
+    opt: &clap::ArgMatches,
+) -> Result<(), Box<dyn Error>> {
+    if opt.is_present("help") {
+        usage::version::show();
+        usage::schedule_downtime::show();
+        return Ok(());
+    };
+
+    let selector = command::filter::ObjectSelector::from_options(opt);
+    let comment = match opt.value_of("comment") {
+        Some(v) => v.to_string(),
+        None => bail!("Downtime comment is mandatory"),
+    };
+
+    let author = match opt.value_of("author") {
+        Some(v) => v.to_string(),
+        None => {
+            match users::get_effective_username() {
+                Some(u) => {
+                    match u.into_string() {
+                        Ok(us) => us,
+                        Err(e) => bail!("Can't convert effective user name into a String: {:?}", e)
+                    }
+                }
+                None => bail!("Can't get effective user name from operating system. Please provide the author of the downtime using the --author option"),
+            }
+        }
+    };
+
+    let duration = match opt.value_of("duration") {
+        Some(v) => match time_parser::parse_duration(v) {
+            Ok(vv) => {
+                if vv > 0 {
+                    vv
+                } else {
+                    bail!("A duration of 0 or less is rather pointless");
+                }
+            }
+            Err(e) => {
+                bail!("Can't parse duration: {}", e);
+            }
+        },
+        None => -1,
+    };
+
+    let fixed = opt.is_present("fixed");
+    let child_opts = command::add_downtime::parse_child_options(opt.value_of("child_opts"))?;
+
+    let mut ranges = Vec::new();
+    if let Some(values) = opt.values_of("range") {
+        for v in values {
+            match v.find('=') {
+                Some(i) => ranges.push((v[..i].trim().to_string(), v[i + 1..].trim().to_string())),
+                None => bail!("Invalid range {}, expected <day>=<time ranges>", v),
+            };
+        }
+    }
+
+    if ranges.is_empty() {
+        bail!("At least one time range is mandatory");
+    }
+
+    if !fixed && duration < 0 {
+        bail!("Flexible downtime must have a duration");
+    }
+
+    if selector.is_empty() {
+        bail!("Provide a host, service, group and/or filter for downtime scheduling");
+    }
+
+    if opt.is_present("config") {
+        let name = match opt.value_of("name") {
+            Some(v) => v.to_string(),
+            None => bail!("The name of the ScheduledDowntime object is mandatory"),
+        };
+
+        let (obj_type, filter) = command::filter::build_object_filter(&selector);
+        let config = build_config(
+            &name, &selector, &author, &comment, fixed, duration, child_opts, &ranges,
+        );
+
+        if !command::objects::preflight(
+            cfg,
+            &format!("{}/{}", constants::ICINGA2_CONFIG_STAGES, constants::NAME),
+            obj_type,
+            &filter,
+            config.trim_end(),
+        )? {
+            return Ok(());
+        }
+
+        let mut files = BTreeMap::new();
+        files.insert(command::config_package::config_file_name(&name), config);
+
+        let (stage, status) = command::config_package::deploy(cfg, files)?;
+        println!(
+            "{}{}: stage {} of package {}: {}",
+            util::instance_prefix(cfg),
+            name,
+            stage,
+            constants::NAME,
+            status
+        );
+
+        return Ok(());
+    }
+
+    let count = match opt.value_of("count") {
+        Some(v) => match u64::from_str(v) {
+            Ok(0) => bail!("Number of downtimes must be greater than 0"),
+            Ok(vv) => vv as usize,
+            Err(e) => bail!("Can't convert {} into a number: {}", v, e),
+        },
+        None => 1,
+    };
+
+    let mut parsed = Vec::new();
+    for (day, times) in &ranges {
+        parsed.push(TimeRange::parse(day, times)?);
+    }
+
+    let windows = next_windows(&parsed, Local::now(), count)?;
+
+    let (dwn_type, filter) = command::filter::build_object_filter(&selector);
+    let mut payloads = Vec::new();
+    for (start, end) in windows {
+        payloads.push(serde_json::to_string(&json_data::AddDowntimeRequest {
+            object_type: dwn_type.to_string(),
+            filter: filter.expression(),
+            filter_vars: filter.vars(),
+            author: author.clone(),
+            comment: comment.clone(),
+            start_time: start,
+            end_time: end,
+            fixed,
+            duration: if duration > 0 { Some(duration) } else { None },
+            all_services: false,
+            trigger_name: String::new(),
+            child_options: child_opts.to_string(),
+        })?);
+    }
+
+    if cfg.debug {
+        eprintln!("HTTP method: POST");
+        eprintln!("URL: {}{}", cfg.url, constants::ICINGA2_ADD_DOWNTIME);
+        for p in &payloads {
+            eprintln!("Payload: {}", p);
+        }
+    }
+
+    if !command::objects::preflight_all(
+        cfg,
+        constants::ICINGA2_ADD_DOWNTIME,
+        dwn_type,
+        &filter,
+        &payloads,
+    )? {
+        return Ok(());
+    }
+
+    for p in payloads {
+        command::add_downtime::schedule(cfg, p)?;
+    }
+
+    Ok(())
+}
+
+// Days matched by the key of an Icinga2 time range, see the documentation of TimePeriod objects
+#[derive(Debug, PartialEq)]
+enum DaySpec {
+    // monday
+    Weekday(Weekday),
+    // monday 1 (first monday of the month), monday -1 (last monday of the month)
+    NthWeekday(Weekday, i64),
+    // day 15, day -1 (last day of the month)
+    MonthDay(i64),
+    // 2021-12-24
+    Date(NaiveDate),
+}
+
+#[derive(Debug, PartialEq)]
+struct TimeRange {
+    day: DaySpec,
+    // start and end of the intervals in seconds since midnight. An end before the start
+    // is on the next day.
+    intervals: Vec<(i64, i64)>,
+}
+
+impl TimeRange {
+    fn parse(day: &str, times: &str) -> Result<TimeRange, Box<dyn Error>> {
+        let day = day.to_lowercase();
+        let fields: Vec<&str> = day.split_whitespace().collect();
+
+        let spec = match fields.as_slice() {
+            ["day", n] => match i64::from_str(n) {
+                Ok(v) if v != 0 && v.abs() <= 31 => DaySpec::MonthDay(v),
+                _ => bail!("Invalid day of month in time range {}", day),
+            },
+            [d] => match Weekday::from_str(d) {
+                Ok(w) => DaySpec::Weekday(w),
+                Err(_) => match NaiveDate::parse_from_str(d, "%Y-%m-%d") {
+                    Ok(v) => DaySpec::Date(v),
+                    Err(_) => bail!("Unsupported time range {}, use --config to let Icinga2 handle the time range", day),
+                },
+            },
+            [d, n] => match (Weekday::from_str(d), i64::from_str(n)) {
+                (Ok(w), Ok(v)) if v != 0 && v.abs() <= 5 => DaySpec::NthWeekday(w, v),
+                _ => bail!("Unsupported time range {}, use --config to let Icinga2 handle the time range", day),
+            },
+            _ => bail!("Unsupported time range {}, use --config to let Icinga2 handle the time range", day),
+        };
+
+        let mut intervals = Vec::new();
+        for t in times.split(',') {
+            let t = t.trim();
+            let (start, end) = match t.find('-') {
+                Some(i) => (parse_time_of_day(&t[..i])?, parse_time_of_day(&t[i + 1..])?),
+                None => bail!("Invalid time range {}, expected HH:MM-HH:MM", t),
+            };
+            if start == end {
+                bail!("Empty time range {}", t);
+            }
+            intervals.push((start, end));
+        }
+
+        Ok(TimeRange {
+            day: spec,
+            intervals,
+        })
+    }
+
+    fn matches(&self, d: NaiveDate) -> bool {
+        match self.day {
+            DaySpec::Weekday(w) => d.weekday() == w,
+            DaySpec::NthWeekday(w, n) => {
+                let nth = if n > 0 {
+                    (d.day() as i64 - 1) / 7 + 1
+                } else {
+                    -((days_in_month(d) - d.day() as i64) / 7 + 1)
+                };
+                d.weekday() == w && nth == n
+            }
+            DaySpec::MonthDay(n) => {
+                if n > 0 {
+                    d.day() as i64 == n
+                } else {
+                    d.day() as i64 == days_in_month(d) + n + 1
+                }
+            }
+            DaySpec::Date(v) => d == v,
+        }
+    }
+}
+
+// HH:MM in seconds since midnight, 24:00 is the end of the day
+fn parse_time_of_day(s: &str) -> Result<i64, Box<dyn Error>> {
+    let s = s.trim();
+    if s == "24:00" {
+        return Ok(86400);
+    }
+
+    match NaiveTime::parse_from_str(s, "%H:%M") {
+        Ok(v) => Ok(v.num_seconds_from_midnight() as i64),
+        Err(_) => bail!("Invalid time {}, expected HH:MM", s),
+    }
+}
+
+fn days_in_month(d: NaiveDate) -> i64 {
+    let (y, m) = if d.month() == 12 {
+        (d.year() + 1, 1)
+    } else {
+        (d.year(), d.month() + 1)
+    };
+
+    match NaiveDate::from_ymd_opt(y, m, 1).and_then(|v| v.pred_opt()) {
+        Some(v) => v.day() as i64,
+        None => 31,
+    }
+}
+
+// Start and end of the next count downtime windows. Windows which already started but
+// didn't end yet are included.
+fn next_windows(
+    ranges: &[TimeRange],
+    now: DateTime<Local>,
+    count: usize,
+) -> Result<Vec<(i64, i64)>, Box<dyn Error>> {
+    let mut windows: Vec<(i64, i64)> = Vec::new();
+    let today = now.date_naive();
+
+    // start yesterday to catch windows spanning midnight
+    for offset in -1..MAX_DAYS {
+        let day = today + Duration::days(offset);
+        let mut found = Vec::new();
+
+        for r in ranges.iter().filter(|r| r.matches(day)) {
+            for (start, end) in &r.intervals {
+                let end = if end < start { end + 86400 } else { *end };
+                let midnight = day.and_time(NaiveTime::MIN);
+                let label = day.format("%Y-%m-%d").to_string();
+
+                let s =
+                    time_parser::local_timestamp(&(midnight + Duration::seconds(*start)), &label)?;
+                let e = time_parser::local_timestamp(&(midnight + Duration::seconds(end)), &label)?;
+
+                if e > now.timestamp() {
+                    found.push((s, e));
+                }
+            }
+        }
+
+        found.sort_unstable();
+        for w in found {
+            if !windows.contains(&w) {
+                windows.push(w);
+            }
+        }
+
+        if windows.len() >= count {
+            windows.truncate(count);
+            return Ok(windows);
+        }
+    }
+
+    if windows.is_empty() {
+        bail!("Time ranges don't match any day in the future");
+    }
+
+    Ok(windows)
+}
+
+// ScheduledDowntime apply rule for the selected objects
+#[allow(clippy::too_many_arguments)]
+fn build_config(
+    name: &str,
+    selector: &command::filter::ObjectSelector,
+    author: &str,
+    comment: &str,
+    fixed: bool,
+    duration: i64,
+    child_opts: &str,
+    ranges: &[(String, String)],
+) -> String {
+    let q = command::config_package::dsl_string;
+
+    let mut assign = Vec::new();
+    if !selector.hosts.is_empty() {
+        assign.push(format!("match({}, host.name)", q(&selector.hosts)));
+    }
+    if !selector.services.is_empty() {
+        assign.push(format!("match({}, service.name)", q(&selector.services)));
+    }
+    if !selector.hostgroups.is_empty() {
+        assign.push(format!(
+            "match({}, host.groups, MatchAny)",
+            q(&selector.hostgroups)
+        ));
+    }
+    if !selector.servicegroups.is_empty() {
+        assign.push(format!(
+            "match({}, service.groups, MatchAny)",
+            q(&selector.servicegroups)
+        ));
+    }
+    if !selector.expression.is_empty() {
+        assign.push(format!("({})", selector.expression));
+    }
+
+    let mut config = format!(
+        "apply ScheduledDowntime {} to {} {{\n",
+        q(name),
+        selector.object_type()
+    );
+    config.push_str(&format!("  author = {}\n", q(author)));
+    config.push_str(&format!("  comment = {}\n", q(comment)));
+    config.push_str(&format!("  fixed = {}\n", fixed));
+    if duration > 0 {
+        config.push_str(&format!("  duration = {}\n", duration));
+    }
+    config.push_str(&format!("  child_options = {}\n", q(child_opts)));
+    config.push_str("  ranges = {\n");
+    for (day, times) in ranges {
+        config.push_str(&format!("    {} = {}\n", q(day), q(times)));
+    }
+    config.push_str("  }\n");
+    config.push_str(&format!("  assign where {}\n", assign.join(" && ")));
+    config.push_str("}\n");
+
+    config
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn local(y: i32, m: u32, d: u32, h: u32, mi: u32) -> i64 {
+        Local
+            .with_ymd_and_hms(y, m, d, h, mi, 0)
+            .unwrap()
+            .timestamp()
+    }
+
+    #[test]
+    fn parse_ranges() {
+        assert_eq!(
+            TimeRange::parse("Monday", "02:00-04:00").unwrap(),
+            TimeRange {
+                day: DaySpec::Weekday(Weekday::Mon),
+                intervals: vec![(7200, 14400)],
+            }
+        );
+        assert_eq!(
+            TimeRange::parse("tuesday 2", "22:00-24:00,00:00-01:30")
+                .unwrap()
+                .intervals,
+            vec![(79200, 86400), (0, 5400)]
+        );
+        assert_eq!(
+            TimeRange::parse("day -1", "23:00-01:00").unwrap().day,
+            DaySpec::MonthDay(-1)
+        );
+
+        for (d, t) in &[
+            ("monday", "02:00"),
+            ("monday", "02:00-02:00"),
+            ("day 0", "02:00-03:00"),
+            ("day 32", "02:00-03:00"),
+            ("monday 6", "02:00-03:00"),
+            ("june 1", "02:00-03:00"),
+            ("2021-02-30", "02:00-03:00"),
+        ] {
+            assert!(
+                TimeRange::parse(d, t).is_err(),
+                "{}={} must be invalid",
+                d,
+                t
+            );
+        }
+    }
+
+    #[test]
+    fn weekly_windows() {
+        // 2020-12-24 is a Thursday
+        let now = Local.with_ymd_and_hms(2020, 12, 24, 10, 30, 0).unwrap();
+        let ranges = vec![TimeRange::parse("monday", "02:00-04:00").unwrap()];
+
+        assert_eq!(
+            next_windows(&ranges, now, 2).unwrap(),
+            vec![
+                (local(2020, 12, 28, 2, 0), local(2020, 12, 28, 4, 0)),
+                (local(2021, 1, 4, 2, 0), local(2021, 1, 4, 4, 0)),
+            ]
+        );
+    }
+
+    #[test]
+    fn running_and_overnight_windows() {
+        let now = Local.with_ymd_and_hms(2020, 12, 24, 0, 30, 0).unwrap();
+        let ranges = vec![
+            TimeRange::parse("wednesday", "23:00-01:00").unwrap(),
+            TimeRange::parse("thursday", "12:00-13:00").unwrap(),
+        ];
+
+        assert_eq!(
+            next_windows(&ranges, now, 2).unwrap(),
+            vec![
+                (local(2020, 12, 23, 23, 0), local(2020, 12, 24, 1, 0)),
+                (local(2020, 12, 24, 12, 0), local(2020, 12, 24, 13, 0)),
+            ]
+        );
+    }
+
+    #[test]
+    fn monthly_windows() {
+        let now = Local.with_ymd_and_hms(2020, 12, 24, 10, 30, 0).unwrap();
+
+        // second tuesday of the month
+        let ranges = vec![TimeRange::parse("tuesday 2", "20:00-22:00").unwrap()];
+        assert_eq!(
+            next_windows(&ranges, now, 2).unwrap(),
+            vec![
+                (local(2021, 1, 12, 20, 0), local(2021, 1, 12, 22, 0)),
+                (local(2021, 2, 9, 20, 0), local(2021, 2, 9, 22, 0)),
+            ]
+        );
+
+        // last friday and last day of the month
+        let ranges = vec![
+            TimeRange::parse("friday -1", "20:00-22:00").unwrap(),
+            TimeRange::parse("day -1", "06:00-07:00").unwrap(),
+        ];
+        assert_eq!(
+            next_windows(&ranges, now, 3).unwrap(),
+            vec![
+                (local(2020, 12, 25, 20, 0), local(2020, 12, 25, 22, 0)),
+                (local(2020, 12, 31, 6, 0), local(2020, 12, 31, 7, 0)),
+                (local(2021, 1, 29, 20, 0), local(2021, 1, 29, 22, 0)),
+            ]
+        );
+
+        // dates in the past don't match
+        let ranges = vec![TimeRange::parse("2020-12-01", "20:00-22:00").unwrap()];
+        assert!(next_windows(&ranges, now, 1).is_err());
+    }
+
+    #[test]
+    fn scheduled_downtime_config() {
+        let selector = command::filter::ObjectSelector {
+            hosts: "db*".to_string(),
+            services: "backup".to_string(),
+            hostgroups: "linux\"".to_string(),
+            expression: "host.vars.sla == 1".to_string(),
+            ..Default::default()
+        };
+        let ranges = vec![("monday".to_string(), "02:00-04:00".to_string())];
+
+        assert_eq!(
+            build_config(
+                "patch-day",
+                &selector,
+                "ops",
+                "Patch day",
+                false,
+                3600,
+                "DowntimeNoChildren",
+                &ranges
+            ),
+            r#"apply ScheduledDowntime "patch-day" to Service {
+  author = "ops"
+  comment = "Patch day"
+  fixed = false
+  duration = 3600
+  child_options = "DowntimeNoChildren"
+  ranges = {
+    "monday" = "02:00-04:00"
+  }
+  assign where match("db*", host.name) && match("backup", service.name) && match("linux\"", host.groups, MatchAny) && (host.vars.sla == 1)
+}
+"#
+        );
+    }
+}
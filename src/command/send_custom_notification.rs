@@ -0,0 +1,113 @@
+use crate::command;
+use crate::configuration;
+use crate::constants;
+use crate::json_data;
+use crate::request;
+use crate::usage;
+use crate::util;
+
+use http::StatusCode;
+use std::error::Error;
+
+pub fn run(
+    cfg: &configuration::Configuration,
+    opt: &clap::ArgMatches,
+) -> Result<(), Box<dyn Error>> {
+    if opt.is_present("help") {
+        usage::version::show();
+        usage::send_custom_notification::show();
+        return Ok(());
+    };
+
+    let selector = command::filter::ObjectSelector::from_options(opt);
+    let comment = match opt.value_of("comment") {
+        Some(v) => v.to_string(),
+        None => bail!("Notification comment is mandatory"),
+    };
+
+    let author = match opt.value_of("author") {
+        Some(v) => v.to_string(),
+        None => {
+            match users::get_effective_username() {
+                Some(u) => {
+                    match u.into_string() {
+                        Ok(us) => us,
+                        Err(e) => bail!("Can't convert effective user name into a String: {:?}", e)
+                    }
+                }
+                None => bail!("Can't get effective user name from operating system. Please provide the author of the notification using the --author option"),
+            }
+        }
+    };
+
+    if selector.is_empty() {
+        bail!("Provide a host, service, group and/or filter for the notification");
+    }
+
+    let (notify_type, filter) = command::filter::build_object_filter(&selector);
+
+    let payload = serde_json::to_string(&json_data::SendCustomNotificationRequest {
+        object_type: notify_type.to_string(),
+        filter: filter.expression(),
+        filter_vars: filter.vars(),
+        author,
+        comment,
+        force: opt.is_present("force"),
+    })?;
+
+    if cfg.debug {
+        eprintln!("HTTP method: POST");
+        eprintln!(
+            "URL: {}{}",
+            cfg.url,
+            constants::ICINGA2_SEND_CUSTOM_NOTIFICATION
+        );
+        eprintln!("Payload: {}", payload);
+    }
+
+    if !command::objects::preflight(
+        cfg,
+        constants::ICINGA2_SEND_CUSTOM_NOTIFICATION,
+        notify_type,
+        &filter,
+        &payload,
+    )? {
+        return Ok(());
+    }
+
+    let req = request::build_client(cfg, "")?
+        .post(
+            format!(
+                "{url}{path}",
+                url = cfg.url,
+                path = constants::ICINGA2_SEND_CUSTOM_NOTIFICATION,
+            )
+            .as_str(),
+        )
+        .body(payload)
+        .send()?;
+
+    if req.status() != StatusCode::OK && req.status() != StatusCode::INTERNAL_SERVER_ERROR {
+        let reason = req.status().canonical_reason().unwrap_or("None");
+        bail!(
+            "Invalid status code received, exepected HTTP status code 200 or 500, got \"{}\" instead",
+            reason
+        );
+    }
+
+    let raw = req.text()?;
+    let action_result: json_data::Icinga2ActionReply = serde_json::from_str(&raw)?;
+
+    for ar in action_result.results {
+        match ar.name {
+            Some(v) => {
+                println!("{}{}: {}", util::instance_prefix(cfg), v, ar.status);
+            }
+            None => {
+                println!("{}{}", util::instance_prefix(cfg), ar.status);
+            }
+        };
+    }
+
+    Ok(())
+}
@@ -0,0 +1,195 @@
+use crate::command;
+use crate::configuration;
+use crate::json_data;
+use crate::usage;
+use crate::util;
+
+use std::collections::BTreeMap;
+use std::error::Error;
+
+// Attributes which can be changed by set-attr and the object attributes of Icinga2
+const ATTRIBUTES: [(&str, &str); 6] = [
+    ("active-checks", "enable_active_checks"),
+    ("event-handler", "enable_event_handler"),
+    ("flapping", "enable_flapping"),
+    ("notifications", "enable_notifications"),
+    ("passive-checks", "enable_passive_checks"),
+    ("perfdata", "enable_perfdata"),
+];
+
+// Attributes to set, attributes to restore and the description of the changes
+type Changes = (
+    BTreeMap<String, serde_json::Value>,
+    Vec<String>,
+    Vec<String>,
+);
+
+pub fn run(
+    cfg: &configuration::Configuration,
+    opt: &clap::ArgMatches,
+) -> Result<(), Box<dyn Error>> {
+    if opt.is_present("help") {
+        usage::version::show();
+        usage::set_attr::show();
+        return Ok(());
+    };
+
+    let selector = command::filter::ObjectSelector::from_options(opt);
+
+    let enable: Vec<&str> = opt.values_of("enable").unwrap_or_default().collect();
+    let disable: Vec<&str> = opt.values_of("disable").unwrap_or_default().collect();
+    let revert: Vec<&str> = opt.values_of("revert").unwrap_or_default().collect();
+
+    let (attrs, restore_attrs, changes) = build_attrs(&enable, &disable, &revert)?;
+
+    if selector.is_empty() {
+        bail!("Provide a host, service, group and/or filter to set attributes for");
+    }
+
+    let (obj_type, filter) = command::filter::build_object_filter(&selector);
+
+    let payload = serde_json::to_string(&json_data::ModifyObjectRequest {
+        filter: filter.expression(),
+        filter_vars: filter.vars(),
+        attrs,
+        restore_attrs,
+    })?;
+
+    if !command::objects::preflight(
+        cfg,
+        command::objects::modify_path(obj_type)?,
+        obj_type,
+        &filter,
+        &payload,
+    )? {
+        return Ok(());
+    }
+
+    let result = command::objects::modify(cfg, obj_type, payload)?;
+
+    for r in result.results {
+        match r.name {
+            Some(v) if r.code.round() as i64 == 200 => {
+                println!(
+                    "{}{}: {}",
+                    util::instance_prefix(cfg),
+                    v,
+                    changes.join(", ")
+                );
+            }
+            Some(v) => {
+                println!("{}{}: {}", util::instance_prefix(cfg), v, r.status);
+            }
+            None => {
+                println!("{}{}", util::instance_prefix(cfg), r.status);
+            }
+        };
+    }
+
+    Ok(())
+}
+
+fn attribute(name: &str) -> Result<&'static str, Box<dyn Error>> {
+    match ATTRIBUTES.iter().find(|(n, _)| *n == name) {
+        Some((_, attr)) => Ok(attr),
+        None => bail!(
+            "Invalid attribute {}, supported attributes are {}",
+            name,
+            ATTRIBUTES
+                .iter()
+                .map(|(n, _)| *n)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    }
+}
+
+// An attribute can only be changed once, all reverts all supported attributes
+fn build_attrs(
+    enable: &[&str],
+    disable: &[&str],
+    revert: &[&str],
+) -> Result<Changes, Box<dyn Error>> {
+    let mut attrs = BTreeMap::new();
+    let mut restore_attrs = Vec::new();
+    let mut changes = Vec::new();
+    let mut seen = Vec::new();
+
+    let revert: Vec<&str> = if revert.contains(&"all") {
+        ATTRIBUTES.iter().map(|(n, _)| *n).collect()
+    } else {
+        revert.to_vec()
+    };
+
+    for (names, value, state) in &[(enable, true, "enabled"), (disable, false, "disabled")] {
+        for name in names.iter() {
+            let attr = attribute(name)?;
+            if seen.contains(&attr) {
+                bail!("Attribute {} can only be changed once", name);
+            }
+            seen.push(attr);
+
+            attrs.insert(attr.to_string(), serde_json::Value::from(*value));
+            changes.push(format!("{} {}", name, state));
+        }
+    }
+
+    for name in revert {
+        let attr = attribute(name)?;
+        if seen.contains(&attr) {
+            bail!("Attribute {} can only be changed once", name);
+        }
+        seen.push(attr);
+
+        restore_attrs.push(attr.to_string());
+        changes.push(format!("{} reverted", name));
+    }
+
+    if changes.is_empty() {
+        bail!("Provide the attributes to enable, disable or revert");
+    }
+
+    Ok((attrs, restore_attrs, changes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn attributes() {
+        let (attrs, restore, changes) = build_attrs(
+            &["active-checks"],
+            &["perfdata", "flapping"],
+            &["event-handler"],
+        )
+        .unwrap();
+
+        assert_eq!(
+            serde_json::to_string(&attrs).unwrap(),
+            "{\"enable_active_checks\":true,\"enable_flapping\":false,\"enable_perfdata\":false}"
+        );
+        assert_eq!(restore, vec!["enable_event_handler"]);
+        assert_eq!(
+            changes,
+            vec![
+                "active-checks enabled",
+                "perfdata disabled",
+                "flapping disabled",
+                "event-handler reverted"
+            ]
+        );
+
+        let (attrs, restore, _) = build_attrs(&[], &[], &["all"]).unwrap();
+        assert!(attrs.is_empty());
+        assert_eq!(restore.len(), ATTRIBUTES.len());
+    }
+
+    #[test]
+    fn invalid_attributes() {
+        assert!(build_attrs(&[], &[], &[]).is_err());
+        assert!(build_attrs(&["checks"], &[], &[]).is_err());
+        assert!(build_attrs(&["flapping"], &["flapping"], &[]).is_err());
+        assert!(build_attrs(&["perfdata"], &[], &["all"]).is_err());
+    }
+}
@@ -2,6 +2,7 @@ use crate::command;
 use crate::configuration;
 use crate::constants;
 use crate::json_data;
+use crate::output;
 use crate::request;
 use crate::usage;
 use crate::util;
@@ -13,21 +14,27 @@ use std::error::Error;
 pub fn run(
     cfg: &configuration::Configuration,
     opt: &clap::ArgMatches,
-) -> Result<(), Box<dyn Error>> {
-    let mut obj: &str = "";
-    let mut attrs: &str = "";
-    let mut filter = String::new();
-    let mut color = true;
-    let mut state_map = HashMap::new();
-    let mut ack = -1;
-    let mut state_type_filter = "state_type >= 0";
-
+) -> Result<i32, Box<dyn Error>> {
     if opt.is_present("help") {
         usage::version::show();
         usage::status::show();
-        return Ok(());
+        return Ok(constants::PLUGIN_OK);
     }
 
+    let records = query(cfg, opt)?;
+    show(cfg, opt, &records)
+}
+
+pub fn query(
+    cfg: &configuration::Configuration,
+    opt: &clap::ArgMatches,
+) -> Result<Vec<output::StatusRecord>, Box<dyn Error>> {
+    let obj: &str;
+    let attrs: &str;
+    let mut state_map = HashMap::new();
+    let mut ack = -1;
+    let mut state_type_filter = "state_type >= 0";
+
     if opt.is_present("ok") {
         state_map.insert("== 0".to_string(), String::new());
     }
@@ -41,10 +48,6 @@ pub fn run(
         state_map.insert("==3".to_string(), String::new());
     }
 
-    if opt.is_present("no-color") {
-        color = false;
-    }
-
     if let Some(v) = opt.value_of("state_type") {
         state_type_filter = match v {
             "hard" => "state_type == 1",
@@ -67,65 +70,39 @@ pub fn run(
         };
     };
 
-    let hosts = match opt.value_of("host_object") {
-        Some(v) => v.to_string(),
-        None => String::new(),
-    };
-    let services = match opt.value_of("service_object") {
-        Some(v) => v.to_string(),
-        None => String::new(),
-    };
+    let selector = command::filter::ObjectSelector::from_options(opt);
 
-    if hosts.is_empty() && services.is_empty() {
-        bail!("Provide a host and/or service for status display");
+    if selector.is_empty() {
+        bail!("Provide a host, service, group and/or filter for status display");
     }
 
-    if !hosts.is_empty() && services.is_empty() {
+    let (obj_type, mut filter) = command::filter::build_object_filter(&selector);
+    let flt = obj_type.to_lowercase();
+
+    if obj_type == "Host" {
         // Show host status for hosts
         obj = constants::ICINGA2_OBJ_HOST;
-
-        let state_filter = command::filter::build_state_filter("host", &state_map);
-        let ack_filter = command::filter::build_ack_filter("host", ack);
-
-        filter = format!(
-            "{{\"filter\":\"match(\\\"{}\\\", host.name) && {} && {} && host.{}\"}}",
-            hosts, state_filter, ack_filter, state_type_filter
-        );
-        attrs = "attrs=name&attrs=display_name&attrs=last_check_result&attrs=state&attrs=acknowledgement&attrs=state_type";
-    }
-
-    if hosts.is_empty() && !services.is_empty() {
-        // Show services for all hosts
+        attrs = "attrs=name&attrs=display_name&attrs=last_check_result&attrs=state&attrs=acknowledgement&attrs=state_type&attrs=downtime_depth";
+    } else {
+        // Show services for all hosts or for the selected hosts
         obj = constants::ICINGA2_OBJ_SERVICE;
-
-        let state_filter = command::filter::build_state_filter("service", &state_map);
-        let ack_filter = command::filter::build_ack_filter("service", ack);
-
-        filter = format!(
-            "{{\"filter\":\"match(\\\"{}\\\", service.name) && {} && {} && service.{}\"}}",
-            services, state_filter, ack_filter, state_type_filter,
-        );
-        attrs = "attrs=display_name&attrs=host_name&attrs=last_check_result&attrs=state&attrs=acknowledgement&attrs=state_type";
+        attrs = "attrs=display_name&attrs=host_name&attrs=last_check_result&attrs=state&attrs=acknowledgement&attrs=state_type&attrs=downtime_depth";
     }
 
-    if !hosts.is_empty() && !services.is_empty() {
-        // Show services for hosts
-        obj = constants::ICINGA2_OBJ_SERVICE;
+    filter.add(&command::filter::build_state_filter(&flt, &state_map));
+    filter.add(&command::filter::build_ack_filter(&flt, ack));
+    filter.add(&format!("{}.{}", flt, state_type_filter));
+    let (filter, filter_vars) = filter.into_parts();
 
-        let state_filter = command::filter::build_state_filter("service", &state_map);
-        let ack_filter = command::filter::build_ack_filter("service", ack);
-
-        filter = format!(
-            "{{\"filter\":\"match(\\\"{}\\\", host.name) && match(\\\"{}\\\", service.name) && {} && {} && service.{}\"}}",
-            hosts, services, state_filter, ack_filter, state_type_filter,
-        );
-        attrs = "attrs=display_name&attrs=host_name&attrs=last_check_result&attrs=state&attrs=acknowledgement&attrs=state_type";
-    }
+    let payload = serde_json::to_string(&json_data::Icinga2ObjectQuery {
+        filter,
+        filter_vars,
+    })?;
 
     if cfg.debug {
         eprintln!("HTTP method: GET");
         eprintln!("URL: {}{}?{}", cfg.url, obj, attrs);
-        eprintln!("Payload: {}", filter);
+        eprintln!("Payload: {}", payload);
     }
 
     let req = request::build_client(cfg, "GET")?
@@ -138,7 +115,7 @@ pub fn run(
             )
             .as_str(),
         )
-        .body(filter)
+        .body(payload)
         .send()?;
 
     if req.status() != StatusCode::OK {
@@ -161,39 +138,232 @@ pub fn run(
         }
     };
 
+    let mut records = Vec::new();
+
     for r in status_result.results {
         // if host_name is set, display_name is the name of the service
-        match r.attrs.host_name {
-            Some(v) => {
-                util::print_state(
-                    &format!(
-                        "{host}: {service}: {status}: {output}",
-                        host = v,
-                        service = r.attrs.display_name,
-                        output = r.attrs.last_check_result.output.replace("\n", " "),
-                        status = util::state_to_string(r.attrs.state)
-                    ),
-                    util::state_to_string(r.attrs.state).as_str(),
-                    r.attrs.acknowledgement,
-                    color,
-                );
+        let (host, service) = match r.attrs.host_name {
+            Some(v) => (v, r.attrs.display_name),
+            None => match r.attrs.name {
+                Some(v) => (v, String::new()),
+                None => continue,
+            },
+        };
+
+        let state = if service.is_empty() {
+            util::host_state_to_string(r.attrs.state)
+        } else {
+            util::state_to_string(r.attrs.state)
+        };
+
+        records.push(output::StatusRecord {
+            instance: cfg.instance.clone(),
+            host,
+            service,
+            state,
+            state_type: util::state_type_to_string(r.attrs.state_type),
+            acknowledgement: util::ack_to_string(r.attrs.acknowledgement),
+            output: r.attrs.last_check_result.output,
+            downtime: r.attrs.downtime_depth > 0.0,
+        });
+    }
+
+    Ok(records)
+}
+
+pub fn show(
+    cfg: &configuration::Configuration,
+    opt: &clap::ArgMatches,
+    records: &[output::StatusRecord],
+) -> Result<i32, Box<dyn Error>> {
+    let color = !opt.is_present("no-color");
+
+    if opt.is_present("check") {
+        return Ok(print_check_result(records));
+    }
+
+    if cfg.output != constants::OUTPUT_TEXT {
+        output::print_records(records, cfg.output)?;
+        return Ok(constants::PLUGIN_OK);
+    }
+
+    for r in records {
+        let mut line = if r.service.is_empty() {
+            format!(
+                "{host}: {status}: {output}",
+                host = r.host,
+                status = r.state,
+                output = r.output.replace("\n", " ")
+            )
+        } else {
+            format!(
+                "{host}: {service}: {status}: {output}",
+                host = r.host,
+                service = r.service,
+                status = r.state,
+                output = r.output.replace("\n", " ")
+            )
+        };
+        // records of multiple instances are merged, so the instance must be taken from the record
+        if cfg.show_instance {
+            line = format!("{}: {}", r.instance, line);
+        }
+        util::print_state(&line, &r.state, r.acknowledgement != "no", color);
+    }
+    Ok(constants::PLUGIN_OK)
+}
+
+// Summarise status records as monitoring plugin output and return the plugin exit code.
+// Only unhandled problems, i.e. neither acknowledged nor in a downtime, are used to determine
+// the resulting state.
+pub fn print_check_result(records: &[output::StatusRecord]) -> i32 {
+    let mut counts = [0; 4];
+    let mut acked = 0;
+    let mut downtime = 0;
+    let mut unhandled = 0;
+    let mut worst = constants::PLUGIN_OK;
+
+    for r in records {
+        let state = if r.service.is_empty() {
+            // a host in DOWN state is critical
+            match r.state.as_str() {
+                "UP" => constants::PLUGIN_OK,
+                "DOWN" => constants::PLUGIN_CRITICAL,
+                _ => constants::PLUGIN_UNKNOWN,
             }
-            None => {
-                if let Some(v) = r.attrs.name {
-                    util::print_state(
-                        &format!(
-                            "{host}: {status}: {output}",
-                            host = v,
-                            output = r.attrs.last_check_result.output.replace("\n", " "),
-                            status = util::state_to_string(r.attrs.state)
-                        ),
-                        util::state_to_string(r.attrs.state).as_str(),
-                        r.attrs.acknowledgement,
-                        color,
-                    );
-                }
+        } else {
+            match r.state.as_str() {
+                "OK" => constants::PLUGIN_OK,
+                "WARNING" => constants::PLUGIN_WARNING,
+                "CRITICAL" => constants::PLUGIN_CRITICAL,
+                _ => constants::PLUGIN_UNKNOWN,
             }
+        };
+
+        counts[state as usize] += 1;
+
+        if state == constants::PLUGIN_OK {
+            continue;
         }
+
+        if r.acknowledgement != "no" {
+            acked += 1;
+            continue;
+        }
+
+        if r.downtime {
+            downtime += 1;
+            continue;
+        }
+
+        unhandled += 1;
+        if plugin_state_severity(state) > plugin_state_severity(worst) {
+            worst = state;
+        }
+    }
+
+    if records.is_empty() {
+        worst = constants::PLUGIN_UNKNOWN;
+    }
+
+    println!(
+        "{name} {state} - {total} objects: {ok} OK, {warning} WARNING, {critical} CRITICAL, {unknown} UNKNOWN, {acked} acknowledged, {downtime} in downtime | ok={ok};;;0 warning={warning};;;0 critical={critical};;;0 unknown={unknown};;;0 acknowledged={acked};;;0 downtime={downtime};;;0 unhandled={unhandled};;;0",
+        name = constants::NAME.to_uppercase(),
+        state = plugin_state_to_string(worst),
+        total = records.len(),
+        ok = counts[constants::PLUGIN_OK as usize],
+        warning = counts[constants::PLUGIN_WARNING as usize],
+        critical = counts[constants::PLUGIN_CRITICAL as usize],
+        unknown = counts[constants::PLUGIN_UNKNOWN as usize],
+        acked = acked,
+        downtime = downtime,
+        unhandled = unhandled,
+    );
+
+    worst
+}
+
+// CRITICAL is worse than UNKNOWN, although UNKNOWN has the higher exit code
+pub fn plugin_state_severity(s: i32) -> i32 {
+    match s {
+        constants::PLUGIN_OK => 0,
+        constants::PLUGIN_WARNING => 1,
+        constants::PLUGIN_UNKNOWN => 2,
+        _ => 3,
+    }
+}
+
+pub fn plugin_state_to_string(s: i32) -> &'static str {
+    match s {
+        constants::PLUGIN_OK => "OK",
+        constants::PLUGIN_WARNING => "WARNING",
+        constants::PLUGIN_CRITICAL => "CRITICAL",
+        _ => "UNKNOWN",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(
+        service: &str,
+        state: &str,
+        acknowledgement: &str,
+        downtime: bool,
+    ) -> output::StatusRecord {
+        output::StatusRecord {
+            instance: "default".to_string(),
+            host: "host".to_string(),
+            service: service.to_string(),
+            state: state.to_string(),
+            state_type: "HARD".to_string(),
+            acknowledgement: acknowledgement.to_string(),
+            output: String::new(),
+            downtime,
+        }
+    }
+
+    #[test]
+    fn check_result() {
+        assert_eq!(print_check_result(&[]), constants::PLUGIN_UNKNOWN);
+
+        let records = vec![
+            record("", "UP", "no", false),
+            record("a", "OK", "no", false),
+            record("b", "WARNING", "no", false),
+            record("c", "UNKNOWN", "no", false),
+        ];
+        assert_eq!(print_check_result(&records), constants::PLUGIN_UNKNOWN);
+
+        let records = vec![
+            record("a", "WARNING", "no", false),
+            record("b", "UNKNOWN", "no", false),
+            record("c", "CRITICAL", "no", false),
+        ];
+        assert_eq!(print_check_result(&records), constants::PLUGIN_CRITICAL);
+
+        let records = vec![
+            record("", "DOWN", "no", false),
+            record("a", "OK", "no", false),
+        ];
+        assert_eq!(print_check_result(&records), constants::PLUGIN_CRITICAL);
+    }
+
+    #[test]
+    fn check_result_handled() {
+        let records = vec![
+            record("", "DOWN", "yes", false),
+            record("a", "CRITICAL", "sticky", false),
+            record("b", "CRITICAL", "no", true),
+            record("c", "WARNING", "no", false),
+        ];
+        assert_eq!(print_check_result(&records), constants::PLUGIN_WARNING);
+
+        let records = vec![
+            record("a", "CRITICAL", "yes", false),
+            record("b", "UNKNOWN", "no", true),
+        ];
+        assert_eq!(print_check_result(&records), constants::PLUGIN_OK);
     }
-    Ok(())
 }
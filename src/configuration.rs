@@ -1,21 +1,58 @@
 use crate::constants;
 
-use ini::Ini;
+use ini::{Ini, Properties};
+use std::collections::BTreeMap;
 use std::env;
 use std::error::Error;
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::process::{Command, Stdio};
 use std::str::FromStr;
 use url::Url;
 
+// Configuration keys for the endpoint and the credentials of a single instance
+const INSTANCE_KEYS: [&str; 14] = [
+    "url",
+    "auth",
+    "user",
+    "password",
+    "password_file",
+    "password_command",
+    "password_env",
+    "auth_cert",
+    "auth_cert_key",
+    "auth_cert_password",
+    "auth_cert_password_file",
+    "auth_cert_password_command",
+    "auth_cert_password_env",
+    "ca_file",
+];
+
+#[derive(Clone)]
 pub struct Configuration {
+    pub instance: String,
     pub url: String,
     pub ca_file: String,
     pub insecure_ssl: bool,
     pub auth: u8,
     pub auth_user: String,
     pub auth_password: String,
+    pub auth_password_file: String,
+    pub auth_password_command: String,
+    pub auth_password_env: String,
     pub auth_cert: String,
+    pub auth_cert_key: String,
     pub auth_cert_password: String,
+    pub auth_cert_password_file: String,
+    pub auth_cert_password_command: String,
+    pub auth_cert_password_env: String,
     pub debug: bool,
+    pub dry_run: bool,
+    pub confirm_threshold: u64,
+    pub max_objects: u64,
+    pub force_many: bool,
+    pub output: u8,
+    pub show_instance: bool,
 }
 
 pub fn get_default_user_config_file() -> Result<String, Box<dyn Error>> {
@@ -39,82 +76,354 @@ pub fn get_default_user_config_file() -> Result<String, Box<dyn Error>> {
     Ok(cfg)
 }
 
-pub fn get_configuration(f: &str) -> Result<Configuration, Box<dyn Error>> {
-    let mut config = Configuration {
+pub fn get_configuration(
+    f: &str,
+    instance: &str,
+    overrides: &[(String, String)],
+) -> Result<Configuration, Box<dyn Error>> {
+    let (mut instances, default) = get_instances(f, overrides)?;
+
+    let name = if instance.is_empty() {
+        if default.is_empty() {
+            bail!("Multiple Icinga2 instances are defined but no default instance is set. Select an instance or set the default instance");
+        }
+        default
+    } else {
+        instance.to_string()
+    };
+
+    let mut config = match instances.remove(&name) {
+        Some(v) => v,
+        None => {
+            bail!("Icinga2 instance {} is not defined", name);
+        }
+    };
+
+    validate_configuration(&config)?;
+    resolve_secrets(&mut config)?;
+
+    Ok(config)
+}
+
+// Returns the configuration of the listed instances, an empty list selects all defined instances
+pub fn get_configurations(
+    f: &str,
+    names: &[String],
+    overrides: &[(String, String)],
+) -> Result<Vec<Configuration>, Box<dyn Error>> {
+    let (mut instances, _) = get_instances(f, overrides)?;
+    let mut result = Vec::new();
+
+    let selected = if names.is_empty() {
+        instances.len()
+    } else {
+        names.len()
+    };
+    if selected > 1 {
+        check_instance_overrides(overrides)?;
+    }
+
+    if names.is_empty() {
+        for (_, mut config) in instances {
+            validate_configuration(&config)?;
+            resolve_secrets(&mut config)?;
+            result.push(config);
+        }
+        return Ok(result);
+    }
+
+    for name in names {
+        let mut config = match instances.remove(name) {
+            Some(v) => v,
+            None => {
+                bail!(
+                    "Icinga2 instance {} is not defined or listed more than once",
+                    name
+                );
+            }
+        };
+        validate_configuration(&config)?;
+        resolve_secrets(&mut config)?;
+        result.push(config);
+    }
+
+    Ok(result)
+}
+
+// Returns all defined Icinga2 instances and the name of the default instance.
+// The name of the default instance is empty if no default could be determined.
+// Without a configuration file, only the default instance, defined by the overrides, exists.
+pub fn get_instances(
+    f: &str,
+    overrides: &[(String, String)],
+) -> Result<(BTreeMap<String, Configuration>, String), Box<dyn Error>> {
+    let mut instances = BTreeMap::new();
+    let mut default = String::new();
+    let mut plaintext_secrets = false;
+
+    if f.is_empty() {
+        let mut config = new_configuration(constants::DEFAULT_INSTANCE_NAME);
+        apply_overrides(&mut config, overrides)?;
+        instances.insert(constants::DEFAULT_INSTANCE_NAME.to_string(), config);
+        return Ok((instances, constants::DEFAULT_INSTANCE_NAME.to_string()));
+    }
+
+    let cfg = Ini::load_from_file(f)?;
+
+    for (section, properties) in &cfg {
+        let section_name = match section {
+            Some(v) => v,
+            None => continue,
+        };
+
+        if section_name == constants::NAME {
+            for (key, value) in properties.iter() {
+                if key == "default" {
+                    default = value.to_string();
+                }
+            }
+            continue;
+        }
+
+        let name = if section_name == "icinga2" {
+            constants::DEFAULT_INSTANCE_NAME
+        } else if let Some(v) = section_name.strip_prefix("icinga2:") {
+            let v = v.trim();
+            if v.is_empty() {
+                bail!("Missing instance name in section {}", section_name);
+            }
+            v
+        } else {
+            continue;
+        };
+
+        if instances.contains_key(name) {
+            bail!("Icinga2 instance {} is defined more than once", name);
+        }
+
+        if properties.contains_key("password") || properties.contains_key("auth_cert_password") {
+            plaintext_secrets = true;
+        }
+
+        let mut config = parse_instance(name, properties)?;
+        apply_overrides(&mut config, overrides)?;
+        instances.insert(name.to_string(), config);
+    }
+
+    if plaintext_secrets && is_world_readable(f)? {
+        eprintln!(
+            "Warning: Configuration file {} contains passwords but is readable by everyone",
+            f
+        );
+    }
+
+    if instances.is_empty() {
+        bail!("No Icinga2 instance defined");
+    }
+
+    if default.is_empty() {
+        if instances.contains_key(constants::DEFAULT_INSTANCE_NAME) {
+            default = constants::DEFAULT_INSTANCE_NAME.to_string();
+        } else if instances.len() == 1 {
+            if let Some(v) = instances.keys().next() {
+                default = v.to_string();
+            }
+        }
+    } else if !instances.contains_key(&default) {
+        bail!("Default Icinga2 instance {} is not defined", default);
+    }
+
+    Ok((instances, default))
+}
+
+fn parse_instance(name: &str, properties: &Properties) -> Result<Configuration, Box<dyn Error>> {
+    let mut config = new_configuration(name);
+
+    for (key, value) in properties.iter() {
+        set_value(&mut config, key, value)?;
+    }
+
+    Ok(config)
+}
+
+fn new_configuration(name: &str) -> Configuration {
+    Configuration {
+        instance: name.to_string(),
         url: String::new(),
         ca_file: String::new(),
         insecure_ssl: false,
         auth: constants::AUTH_USER,
         auth_user: String::new(),
         auth_password: String::new(),
+        auth_password_file: String::new(),
+        auth_password_command: String::new(),
+        auth_password_env: String::new(),
         auth_cert: String::new(),
+        auth_cert_key: String::new(),
         auth_cert_password: String::new(),
+        auth_cert_password_file: String::new(),
+        auth_cert_password_command: String::new(),
+        auth_cert_password_env: String::new(),
         debug: false,
-    };
-
-    let cfg = Ini::load_from_file(f)?;
-
-    for (section, properties) in &cfg {
-        let section_name = section.expect("Section is not defined");
+        dry_run: false,
+        confirm_threshold: 0,
+        max_objects: 0,
+        force_many: false,
+        output: constants::OUTPUT_TEXT,
+        show_instance: false,
+    }
+}
 
-        if section_name == "icinga2" {
-            for (key, value) in properties.iter() {
-                match key {
-                    "auth" => {
-                        match value {
-                            "user" => {
-                                config.auth = constants::AUTH_USER;
-                            }
-                            "cert" => {
-                                config.auth = constants::AUTH_CERT;
-                            }
-                            _ => {
-                                bail!("Invalid authentication type {}", value)
-                            }
-                        };
-                    }
-                    "user" => {
-                        config.auth_user = value.to_string();
-                    }
-                    "password" => {
-                        config.auth_password = value.to_string();
-                    }
-                    "auth_cert" => {
-                        config.auth_cert = value.to_string();
-                    }
-                    "auth_cert_password" => {
-                        config.auth_cert_password = value.to_string();
-                    }
-                    "insecure_ssl" => {
-                        config.insecure_ssl = match FromStr::from_str(value) {
-                            Ok(v) => v,
-                            Err(e) => {
-                                bail!(
-                                    "Can't convert value {} for {} into a boolean: {}",
-                                    value,
-                                    key,
-                                    e
-                                );
-                            }
-                        }
-                    }
-                    "ca_file" => {
-                        config.ca_file = value.to_string();
-                    }
-                    "url" => {
-                        config.url = normalize_url(&value.to_string())?;
-                    }
-                    _ => {}
+fn set_value(config: &mut Configuration, key: &str, value: &str) -> Result<(), Box<dyn Error>> {
+    match key {
+        "auth" => {
+            match value {
+                "user" => {
+                    config.auth = constants::AUTH_USER;
+                }
+                "cert" => {
+                    config.auth = constants::AUTH_CERT;
+                }
+                _ => {
+                    bail!("Invalid authentication type {}", value)
+                }
+            };
+        }
+        "user" => {
+            config.auth_user = value.to_string();
+        }
+        "password" => {
+            config.auth_password = value.to_string();
+        }
+        "password_file" => {
+            config.auth_password_file = value.to_string();
+        }
+        "password_command" => {
+            config.auth_password_command = value.to_string();
+        }
+        "password_env" => {
+            config.auth_password_env = value.to_string();
+        }
+        "auth_cert" => {
+            config.auth_cert = value.to_string();
+        }
+        "auth_cert_key" => {
+            config.auth_cert_key = value.to_string();
+        }
+        "auth_cert_password" => {
+            config.auth_cert_password = value.to_string();
+        }
+        "auth_cert_password_file" => {
+            config.auth_cert_password_file = value.to_string();
+        }
+        "auth_cert_password_command" => {
+            config.auth_cert_password_command = value.to_string();
+        }
+        "auth_cert_password_env" => {
+            config.auth_cert_password_env = value.to_string();
+        }
+        "insecure_ssl" => {
+            config.insecure_ssl = match FromStr::from_str(value) {
+                Ok(v) => v,
+                Err(e) => {
+                    bail!(
+                        "Can't convert value {} for {} into a boolean: {}",
+                        value,
+                        key,
+                        e
+                    );
                 }
             }
         }
-    }
+        "ca_file" => {
+            config.ca_file = value.to_string();
+        }
+        "url" => {
+            config.url = normalize_url(value)?;
+        }
+        "confirm_threshold" => {
+            config.confirm_threshold = match u64::from_str(value) {
+                Ok(v) => v,
+                Err(e) => {
+                    bail!(
+                        "Can't convert value {} for {} into a number: {}",
+                        value,
+                        key,
+                        e
+                    );
+                }
+            }
+        }
+        "max_objects" => {
+            config.max_objects = match u64::from_str(value) {
+                Ok(v) => v,
+                Err(e) => {
+                    bail!(
+                        "Can't convert value {} for {} into a number: {}",
+                        value,
+                        key,
+                        e
+                    );
+                }
+            }
+        }
+        _ => {}
+    };
+    Ok(())
+}
 
-    if let Err(e) = validate_configuration(&config) {
-        return Err(e);
+// Overrides are applied in order, so later overrides take precedence. Setting a password
+// replaces the password source from the configuration file or from previous overrides.
+fn apply_overrides(
+    config: &mut Configuration,
+    overrides: &[(String, String)],
+) -> Result<(), Box<dyn Error>> {
+    for (key, value) in overrides {
+        match key.as_str() {
+            "password" | "password_file" | "password_command" | "password_env" => {
+                config.auth_password.clear();
+                config.auth_password_file.clear();
+                config.auth_password_command.clear();
+                config.auth_password_env.clear();
+            }
+            "auth_cert_password"
+            | "auth_cert_password_file"
+            | "auth_cert_password_command"
+            | "auth_cert_password_env" => {
+                config.auth_cert_password.clear();
+                config.auth_cert_password_file.clear();
+                config.auth_cert_password_command.clear();
+                config.auth_cert_password_env.clear();
+            }
+            _ => {}
+        };
+        set_value(config, key, value)?;
     }
+    Ok(())
+}
 
-    Ok(config)
+// The endpoint and the credentials differ for every instance, so they can't be overridden
+// for multiple instances at once
+fn check_instance_overrides(overrides: &[(String, String)]) -> Result<(), Box<dyn Error>> {
+    for (key, _) in overrides {
+        if !INSTANCE_KEYS.contains(&key.as_str()) {
+            continue;
+        }
+
+        match constants::CONFIG_OVERRIDES.iter().find(|(k, _, _)| k == key) {
+            Some((_, option, env_name)) => bail!(
+                "--{} or {} can't be used for multiple instances, set {} in the configuration file instead",
+                option,
+                env_name,
+                key
+            ),
+            None => bail!(
+                "{} can't be overridden for multiple instances, set it in the configuration file instead",
+                key
+            ),
+        };
+    }
+    Ok(())
 }
 
 fn normalize_url(u: &str) -> Result<String, Box<dyn Error>> {
@@ -143,7 +452,7 @@ fn normalize_url(u: &str) -> Result<String, Box<dyn Error>> {
 
 fn validate_configuration(cfg: &Configuration) -> Result<(), Box<dyn Error>> {
     if cfg.url.is_empty() {
-        bail!("Missing Icinga2 URL");
+        bail!("Missing Icinga2 URL for instance {}", cfg.instance);
     }
 
     match cfg.auth {
@@ -152,14 +461,35 @@ fn validate_configuration(cfg: &Configuration) -> Result<(), Box<dyn Error>> {
                 bail!("User authentication enabled but no user set");
             }
 
-            if cfg.auth_password.is_empty() {
-                bail!("User authentication enabled but no password set");
-            }
+            match count_secret_sources(
+                &cfg.auth_password,
+                &cfg.auth_password_file,
+                &cfg.auth_password_command,
+                &cfg.auth_password_env,
+            ) {
+                0 => {
+                    bail!("User authentication enabled but no password set");
+                }
+                1 => {}
+                _ => {
+                    bail!("Only one of password, password_file, password_command or password_env can be set");
+                }
+            };
         }
         constants::AUTH_CERT => {
             if cfg.auth_cert.is_empty() {
                 bail!("Client certificate authentication enabled but no certificate file set");
             }
+
+            if count_secret_sources(
+                &cfg.auth_cert_password,
+                &cfg.auth_cert_password_file,
+                &cfg.auth_cert_password_command,
+                &cfg.auth_cert_password_env,
+            ) > 1
+            {
+                bail!("Only one of auth_cert_password, auth_cert_password_file, auth_cert_password_command or auth_cert_password_env can be set");
+            }
         }
         _ => {
             bail!("Invalid authentication method or authentication method not set");
@@ -167,3 +497,335 @@ fn validate_configuration(cfg: &Configuration) -> Result<(), Box<dyn Error>> {
     };
     Ok(())
 }
+
+fn count_secret_sources(value: &str, file: &str, command: &str, env_name: &str) -> usize {
+    [value, file, command, env_name]
+        .iter()
+        .filter(|s| !s.is_empty())
+        .count()
+}
+
+fn resolve_secrets(cfg: &mut Configuration) -> Result<(), Box<dyn Error>> {
+    if cfg.auth_password.is_empty() {
+        cfg.auth_password = get_secret(
+            &cfg.auth_password_file,
+            &cfg.auth_password_command,
+            &cfg.auth_password_env,
+        )?;
+    }
+
+    if cfg.auth_cert_password.is_empty() {
+        cfg.auth_cert_password = get_secret(
+            &cfg.auth_cert_password_file,
+            &cfg.auth_cert_password_command,
+            &cfg.auth_cert_password_env,
+        )?;
+    }
+
+    if cfg.auth == constants::AUTH_USER && cfg.auth_password.is_empty() {
+        bail!("User authentication enabled but password is empty");
+    }
+
+    Ok(())
+}
+
+// Only the first line of a password file or the output of a password command is used,
+// e.g. pass(1) stores additional information after the first line
+fn get_secret(file: &str, command: &str, env_name: &str) -> Result<String, Box<dyn Error>> {
+    if !file.is_empty() {
+        let content = match fs::read_to_string(file) {
+            Ok(v) => v,
+            Err(e) => {
+                bail!("Can't read password file {}: {}", file, e);
+            }
+        };
+        return Ok(content.lines().next().unwrap_or("").to_string());
+    }
+
+    if !command.is_empty() {
+        // stdin and stderr are passed to the command to allow for interactive unlocking
+        // (e.g. by gpg-agent)
+        let result = match Command::new("sh")
+            .arg("-c")
+            .arg(command)
+            .stdin(Stdio::inherit())
+            .stderr(Stdio::inherit())
+            .output()
+        {
+            Ok(v) => v,
+            Err(e) => {
+                bail!("Can't run password command {}: {}", command, e);
+            }
+        };
+
+        if !result.status.success() {
+            bail!("Password command {} failed: {}", command, result.status);
+        }
+
+        let out = String::from_utf8(result.stdout)?;
+        return Ok(out.lines().next().unwrap_or("").to_string());
+    }
+
+    if !env_name.is_empty() {
+        return match env::var(env_name) {
+            Ok(v) => Ok(v),
+            Err(e) => {
+                bail!(
+                    "Can't read password from environment variable {}: {}",
+                    env_name,
+                    e
+                );
+            }
+        };
+    }
+
+    Ok(String::new())
+}
+
+fn is_world_readable(f: &str) -> Result<bool, Box<dyn Error>> {
+    let mode = fs::metadata(f)?.permissions().mode();
+    Ok(mode & 0o004 != 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::process;
+
+    // Configuration file with the given content, the file is removed when it is dropped
+    struct ConfigFile(String);
+
+    impl ConfigFile {
+        fn new(name: &str, content: &str) -> ConfigFile {
+            let path = env::temp_dir().join(format!(
+                "{}-test-{}-{}.ini",
+                constants::NAME,
+                process::id(),
+                name
+            ));
+            fs::write(&path, content).unwrap();
+            fs::set_permissions(&path, fs::Permissions::from_mode(0o600)).unwrap();
+            ConfigFile(path.to_string_lossy().to_string())
+        }
+    }
+
+    impl Drop for ConfigFile {
+        fn drop(&mut self) {
+            let _ = fs::remove_file(&self.0);
+        }
+    }
+
+    fn overrides(values: &[(&str, &str)]) -> Vec<(String, String)> {
+        values
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    const INSTANCES: &str = "[icinga2:a]
+url = https://a.example.com
+user = a
+password_file = /nonexistent
+
+[icinga2:b]
+url = https://b.example.com:5665
+user = b
+password = b
+";
+
+    #[test]
+    fn override_precedence() {
+        let f = ConfigFile::new("precedence", INSTANCES);
+
+        // environment variables are passed before the command line options
+        let (instances, _) = get_instances(
+            &f.0,
+            &overrides(&[
+                ("url", "https://env.example.com"),
+                ("user", "env"),
+                ("url", "https://cli.example.com"),
+            ]),
+        )
+        .unwrap();
+
+        let a = &instances["a"];
+        assert_eq!(a.url, "https://cli.example.com:443");
+        assert_eq!(a.auth_user, "env");
+        assert_eq!(a.auth_password_file, "/nonexistent");
+
+        let (instances, _) = get_instances(&f.0, &[]).unwrap();
+        assert_eq!(instances["b"].url, "https://b.example.com:5665");
+        assert_eq!(instances["b"].auth_user, "b");
+    }
+
+    #[test]
+    fn override_password_source() {
+        let f = ConfigFile::new("password", INSTANCES);
+
+        let (instances, _) = get_instances(&f.0, &overrides(&[("password", "secret")])).unwrap();
+        let a = &instances["a"];
+        assert_eq!(a.auth_password, "secret");
+        assert!(a.auth_password_file.is_empty());
+        assert!(validate_configuration(a).is_ok());
+
+        let (instances, _) = get_instances(
+            &f.0,
+            &overrides(&[("password", "secret"), ("password_env", "PASSWORD")]),
+        )
+        .unwrap();
+        let b = &instances["b"];
+        assert!(b.auth_password.is_empty());
+        assert_eq!(b.auth_password_env, "PASSWORD");
+        assert!(validate_configuration(b).is_ok());
+    }
+
+    #[test]
+    fn instance_overrides() {
+        let f = ConfigFile::new("instances", INSTANCES);
+        let names = vec!["a".to_string(), "b".to_string()];
+
+        assert!(get_configurations(&f.0, &names, &overrides(&[("url", "https://x")])).is_err());
+        assert!(get_configurations(&f.0, &[], &overrides(&[("password", "x")])).is_err());
+
+        let configs = get_configurations(
+            &f.0,
+            &names,
+            &overrides(&[("password", "x"), ("max_objects", "10")]),
+        );
+        assert!(configs.is_err());
+
+        let configs =
+            get_configurations(&f.0, &names[1..], &overrides(&[("max_objects", "10")])).unwrap();
+        assert_eq!(configs.len(), 1);
+        assert_eq!(configs[0].max_objects, 10);
+
+        let configs = get_configurations(
+            &f.0,
+            &names[..1],
+            &overrides(&[("password", "x"), ("url", "https://x")]),
+        )
+        .unwrap();
+        assert_eq!(configs[0].url, "https://x:443");
+    }
+
+    #[test]
+    fn default_instance() {
+        let f = ConfigFile::new(
+            "default_set",
+            &format!("[icinga2ctl]\ndefault = b\n\n{}", INSTANCES),
+        );
+        let (instances, default) = get_instances(&f.0, &[]).unwrap();
+        assert_eq!(instances.len(), 2);
+        assert_eq!(default, "b");
+
+        // without a default instance, no instance can be selected
+        let f = ConfigFile::new("default_unset", INSTANCES);
+        let (_, default) = get_instances(&f.0, &[]).unwrap();
+        assert!(default.is_empty());
+        assert!(get_configuration(&f.0, "", &[]).is_err());
+        assert_eq!(get_configuration(&f.0, "b", &[]).unwrap().instance, "b");
+
+        // the section [icinga2] is the default instance
+        let f = ConfigFile::new(
+            "default_section",
+            &format!("[icinga2]\nurl = https://d.example.com\n\n{}", INSTANCES),
+        );
+        let (_, default) = get_instances(&f.0, &[]).unwrap();
+        assert_eq!(default, constants::DEFAULT_INSTANCE_NAME);
+
+        // a single instance is the default instance
+        let f = ConfigFile::new("default_single", "[icinga2:x]\nurl = https://x\n");
+        let (_, default) = get_instances(&f.0, &[]).unwrap();
+        assert_eq!(default, "x");
+
+        let f = ConfigFile::new(
+            "default_missing",
+            &format!("[icinga2ctl]\ndefault = c\n\n{}", INSTANCES),
+        );
+        assert!(get_instances(&f.0, &[]).is_err());
+
+        // without a configuration file, the instance is defined by the overrides
+        let (instances, default) =
+            get_instances("", &overrides(&[("url", "https://o.example.com")])).unwrap();
+        assert_eq!(default, constants::DEFAULT_INSTANCE_NAME);
+        assert_eq!(instances[&default].url, "https://o.example.com:443");
+    }
+
+    #[test]
+    fn invalid_instances() {
+        let f = ConfigFile::new(
+            "duplicate",
+            &format!("{}\n[icinga2:a]\nurl = https://x\n", INSTANCES),
+        );
+        assert!(get_instances(&f.0, &[]).is_err());
+
+        let f = ConfigFile::new(
+            "duplicate_default",
+            "[icinga2]\nurl = https://x\n\n[icinga2:default]\nurl = https://y\n",
+        );
+        assert!(get_instances(&f.0, &[]).is_err());
+
+        let f = ConfigFile::new("empty_name", "[icinga2:]\nurl = https://x\n");
+        assert!(get_instances(&f.0, &[]).is_err());
+
+        let f = ConfigFile::new("no_instance", "[icinga2ctl]\n");
+        assert!(get_instances(&f.0, &[]).is_err());
+
+        let f = ConfigFile::new("listed_twice", INSTANCES);
+        let names = vec!["b".to_string(), "b".to_string()];
+        assert!(get_configurations(&f.0, &names, &[]).is_err());
+    }
+
+    #[test]
+    fn validation() {
+        let mut cfg = new_configuration("v");
+        assert!(validate_configuration(&cfg).is_err());
+
+        cfg.url = "https://v.example.com:443".to_string();
+        cfg.auth_user = "v".to_string();
+        assert!(validate_configuration(&cfg).is_err());
+
+        cfg.auth_password_command = "pass show icinga2".to_string();
+        assert!(validate_configuration(&cfg).is_ok());
+
+        cfg.auth_password_env = "PASSWORD".to_string();
+        assert!(validate_configuration(&cfg).is_err());
+
+        let mut cfg = new_configuration("c");
+        cfg.url = "https://c.example.com:443".to_string();
+        cfg.auth = constants::AUTH_CERT;
+        assert!(validate_configuration(&cfg).is_err());
+
+        // the certificate password is optional
+        cfg.auth_cert = "/etc/icinga2ctl/c.p12".to_string();
+        assert!(validate_configuration(&cfg).is_ok());
+
+        cfg.auth_cert_password = "c".to_string();
+        cfg.auth_cert_password_file = "/etc/icinga2ctl/c.pass".to_string();
+        assert!(validate_configuration(&cfg).is_err());
+    }
+
+    #[test]
+    fn secrets() {
+        assert_eq!(get_secret("", "", "").unwrap(), "");
+
+        let f = ConfigFile::new("secret", "first\nsecond\n");
+        assert_eq!(get_secret(&f.0, "", "").unwrap(), "first");
+        assert!(get_secret("/nonexistent/secret", "", "").is_err());
+
+        assert_eq!(
+            get_secret("", "printf 'first\\nsecond\\n'", "").unwrap(),
+            "first"
+        );
+        assert!(get_secret("", "exit 1", "").is_err());
+
+        // a file takes precedence over a command
+        assert_eq!(get_secret(&f.0, "echo command", "").unwrap(), "first");
+
+        assert_eq!(
+            get_secret("", "", "PATH").unwrap(),
+            env::var("PATH").unwrap()
+        );
+        assert!(get_secret("", "", "ICINGA2CTL_TEST_UNSET_PASSWORD").is_err());
+    }
+}
@@ -2,11 +2,63 @@ pub const NAME: &str = "icinga2ctl";
 pub const VERSION: &str = "0.9.0-20201222";
 const PROJECT_URL: &str = "https://git.ypbind.de/cgit/icinga2ctl";
 
+// Configuration keys with their command line option and environment variable
+pub const CONFIG_OVERRIDES: &[(&str, &str, &str)] = &[
+    ("url", "url", "ICINGA2CTL_URL"),
+    ("auth", "auth", "ICINGA2CTL_AUTH"),
+    ("user", "user", "ICINGA2CTL_USER"),
+    ("password", "password", "ICINGA2CTL_PASSWORD"),
+    ("password_file", "password-file", "ICINGA2CTL_PASSWORD_FILE"),
+    (
+        "password_command",
+        "password-command",
+        "ICINGA2CTL_PASSWORD_COMMAND",
+    ),
+    ("password_env", "password-env", "ICINGA2CTL_PASSWORD_ENV"),
+    ("ca_file", "ca-file", "ICINGA2CTL_CA_FILE"),
+    ("insecure_ssl", "insecure-ssl", "ICINGA2CTL_INSECURE_SSL"),
+    ("auth_cert", "auth-cert", "ICINGA2CTL_AUTH_CERT"),
+    ("auth_cert_key", "auth-cert-key", "ICINGA2CTL_AUTH_CERT_KEY"),
+    (
+        "auth_cert_password",
+        "auth-cert-password",
+        "ICINGA2CTL_AUTH_CERT_PASSWORD",
+    ),
+    (
+        "auth_cert_password_file",
+        "auth-cert-password-file",
+        "ICINGA2CTL_AUTH_CERT_PASSWORD_FILE",
+    ),
+    (
+        "auth_cert_password_command",
+        "auth-cert-password-command",
+        "ICINGA2CTL_AUTH_CERT_PASSWORD_COMMAND",
+    ),
+    (
+        "auth_cert_password_env",
+        "auth-cert-password-env",
+        "ICINGA2CTL_AUTH_CERT_PASSWORD_ENV",
+    ),
+    (
+        "confirm_threshold",
+        "confirm-threshold",
+        "ICINGA2CTL_CONFIRM_THRESHOLD",
+    ),
+    ("max_objects", "max-objects", "ICINGA2CTL_MAX_OBJECTS"),
+];
+
+pub const ENV_INSTANCE: &str = "ICINGA2CTL_INSTANCE";
+pub const DEFAULT_INSTANCE_NAME: &str = "default";
+
 pub const AUTH_USER: u8 = 0x01;
 pub const AUTH_CERT: u8 = 0x02;
 
 pub const ICINGA2_OBJ_SERVICE: &str = "/v1/objects/services";
 pub const ICINGA2_OBJ_HOST: &str = "/v1/objects/hosts";
+pub const ICINGA2_OBJ_DOWNTIME: &str = "/v1/objects/downtimes";
+pub const ICINGA2_OBJ_COMMENT: &str = "/v1/objects/comments";
+pub const ICINGA2_OBJ_HOSTGROUP: &str = "/v1/objects/hostgroups";
+pub const ICINGA2_OBJ_SERVICEGROUP: &str = "/v1/objects/servicegroups";
 pub const ICINGA2_ADD_ACK: &str = "/v1/actions/acknowledge-problem";
 pub const ICINGA2_DEL_ACK: &str = "/v1/actions/remove-acknowledgement";
 pub const ICINGA2_ADD_DOWNTIME: &str = "/v1/actions/schedule-downtime";
@@ -15,11 +67,36 @@ pub const ICINGA2_RESCHEDULE_CHECK: &str = "/v1/actions/reschedule-check";
 pub const ICINGA2_GENERATE_TICKET: &str = "/v1/actions/generate-ticket";
 pub const ICINGA2_ADD_COMMENT: &str = "/v1/actions/add-comment";
 pub const ICINGA2_DEL_COMMENT: &str = "/v1/actions/remove-comment";
+pub const ICINGA2_PROCESS_CHECK_RESULT: &str = "/v1/actions/process-check-result";
+pub const ICINGA2_DELAY_NOTIFICATION: &str = "/v1/actions/delay-notification";
+pub const ICINGA2_SEND_CUSTOM_NOTIFICATION: &str = "/v1/actions/send-custom-notification";
+pub const ICINGA2_CONFIG_PACKAGES: &str = "/v1/config/packages";
+pub const ICINGA2_CONFIG_STAGES: &str = "/v1/config/stages";
+pub const ICINGA2_CONFIG_FILES: &str = "/v1/config/files";
+
+// Seconds to wait for Icinga2 to validate a new stage of a config package
+pub const ICINGA2_CONFIG_VALIDATION_TIMEOUT: u64 = 120;
 
 pub const ICINGA2_ACK_NONE: i8 = 0;
 pub const ICINGA2_ACK_ACK: i8 = 1;
 pub const ICINGA2_ACK_STICKY: i8 = 2;
 
+pub const ICINGA2_COMMENT_USER: i8 = 1;
+pub const ICINGA2_COMMENT_DOWNTIME: i8 = 2;
+pub const ICINGA2_COMMENT_FLAPPING: i8 = 3;
+pub const ICINGA2_COMMENT_ACK: i8 = 4;
+
+pub const PLUGIN_OK: i32 = 0;
+pub const PLUGIN_WARNING: i32 = 1;
+pub const PLUGIN_CRITICAL: i32 = 2;
+pub const PLUGIN_UNKNOWN: i32 = 3;
+
+pub const OUTPUT_TEXT: u8 = 0x01;
+pub const OUTPUT_JSON: u8 = 0x02;
+pub const OUTPUT_YAML: u8 = 0x03;
+pub const OUTPUT_CSV: u8 = 0x04;
+pub const OUTPUT_TSV: u8 = 0x05;
+
 pub fn user_agent() -> String {
     format!("{}/{} (+{})", NAME, VERSION, PROJECT_URL)
 }
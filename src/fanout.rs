@@ -0,0 +1,160 @@
+use crate::command;
+use crate::configuration;
+use crate::constants;
+
+use std::error::Error;
+use std::thread;
+
+type Command<T> = fn(&configuration::Configuration, &clap::ArgMatches) -> Result<T, Box<dyn Error>>;
+
+// Run command on all instances and return the merged exit code.
+// Results of commands returning objects are merged into a single result.
+pub fn run_command(
+    configs: &[configuration::Configuration],
+    cmd: &str,
+    opt: &clap::ArgMatches<'static>,
+) -> i32 {
+    // global options are the same for all instances
+    let cfg = &configs[0];
+
+    match cmd {
+        "add-ack" => run_action(configs, opt, command::add_ack::run),
+        "add-comment" => run_action(configs, opt, command::add_comment::run),
+        "add-downtime" => run_action(configs, opt, command::add_downtime::run),
+        "del-ack" => run_action(configs, opt, command::del_ack::run),
+        "del-comment" => run_action(configs, opt, command::del_comment::run),
+        "del-downtime" => run_action(configs, opt, command::del_downtime::run),
+        "delay-notification" => run_action(configs, opt, command::delay_notification::run),
+        "disable-notifications" => run_action(configs, opt, command::notifications::disable),
+        "enable-notifications" => run_action(configs, opt, command::notifications::enable),
+        "generate-ticket" => run_action(configs, opt, command::generate_ticket::run),
+        "list-acks" => {
+            let (records, rc) = merge(run(configs, opt, command::list_acks::query));
+            show_result(command::list_comments::show(cfg, &records), rc)
+        }
+        "list-comments" => {
+            let (records, rc) = merge(run(configs, opt, command::list_comments::query));
+            show_result(command::list_comments::show(cfg, &records), rc)
+        }
+        "list-downtimes" => {
+            let (records, rc) = merge(run(configs, opt, command::list_downtimes::query));
+            show_result(command::list_downtimes::show(cfg, &records), rc)
+        }
+        "list-groups" => {
+            let (records, rc) = merge(run(configs, opt, command::list_groups::query));
+            show_result(command::list_groups::show(cfg, &records), rc)
+        }
+        "modify-downtime" => run_action(configs, opt, command::modify_downtime::run),
+        "process-check-result" => run_action(configs, opt, command::process_check_result::run),
+        "reschedule-check" => run_action(configs, opt, command::reschedule_check::run),
+        "schedule-downtime" => run_action(configs, opt, command::schedule_downtime::run),
+        "send-custom-notification" => {
+            run_action(configs, opt, command::send_custom_notification::run)
+        }
+        "set-attr" => run_action(configs, opt, command::set_attr::run),
+        "status" => {
+            let results = run(configs, opt, command::status::query);
+
+            // a failed query makes the result of the check unusable
+            if opt.is_present("check") {
+                let errors: Vec<String> = results
+                    .iter()
+                    .filter_map(|(name, r)| match r {
+                        Ok(_) => None,
+                        Err(e) => Some(format!("{}: {}", name, e)),
+                    })
+                    .collect();
+
+                if !errors.is_empty() {
+                    println!(
+                        "{} UNKNOWN - {}",
+                        constants::NAME.to_uppercase(),
+                        errors.join(", ")
+                    );
+                    return constants::PLUGIN_UNKNOWN;
+                }
+            }
+
+            let (records, rc) = merge(results);
+            match command::status::show(cfg, opt, &records) {
+                Ok(v) => std::cmp::max(v, rc),
+                Err(e) => {
+                    println!("Error: {}", e);
+                    1
+                }
+            }
+        }
+        _ => {
+            println!("Error: Command {} can't be run on multiple instances", cmd);
+            1
+        }
+    }
+}
+
+// Run command concurrently on all instances, the results are returned in the order of the instances
+fn run<T: Send + 'static>(
+    configs: &[configuration::Configuration],
+    opt: &clap::ArgMatches<'static>,
+    cmd: Command<T>,
+) -> Vec<(String, Result<T, String>)> {
+    let mut threads = Vec::new();
+
+    for cfg in configs {
+        let cfg = cfg.clone();
+        let opt = opt.clone();
+        threads.push((
+            cfg.instance.clone(),
+            thread::spawn(move || cmd(&cfg, &opt).map_err(|e| e.to_string())),
+        ));
+    }
+
+    threads
+        .into_iter()
+        .map(|(name, t)| match t.join() {
+            Ok(v) => (name, v),
+            Err(_) => (name, Err("Command aborted unexpectedly".to_string())),
+        })
+        .collect()
+}
+
+fn run_action(
+    configs: &[configuration::Configuration],
+    opt: &clap::ArgMatches<'static>,
+    cmd: Command<()>,
+) -> i32 {
+    let mut rc = 0;
+
+    for (name, result) in run(configs, opt, cmd) {
+        if let Err(e) = result {
+            println!("Error: {}: {}", name, e);
+            rc = 1;
+        }
+    }
+    rc
+}
+
+fn merge<T>(results: Vec<(String, Result<Vec<T>, String>)>) -> (Vec<T>, i32) {
+    let mut merged = Vec::new();
+    let mut rc = 0;
+
+    for (name, result) in results {
+        match result {
+            Ok(v) => merged.extend(v),
+            Err(e) => {
+                println!("Error: {}: {}", name, e);
+                rc = 1;
+            }
+        }
+    }
+    (merged, rc)
+}
+
+fn show_result(result: Result<(), Box<dyn Error>>, rc: i32) -> i32 {
+    match result {
+        Ok(_) => rc,
+        Err(e) => {
+            println!("Error: {}", e);
+            1
+        }
+    }
+}
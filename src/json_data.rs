@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 #[derive(Serialize, Deserialize)]
 pub struct Icinga2Status {
@@ -15,6 +16,8 @@ pub struct Icinga2StatusResult {
 pub struct StatusResultAttrs {
     pub acknowledgement: f64,
     pub display_name: String,
+    #[serde(default)]
+    pub downtime_depth: f64,
     pub host_name: Option<String>,
     pub last_check_result: LastCheckResult,
     pub name: Option<String>,
@@ -35,8 +38,531 @@ pub struct Icinga2ActionReply {
 
 #[derive(Serialize, Deserialize)]
 pub struct Icinga2ActionReplyResult {
-    code: f64,
+    pub code: f64,
     pub name: Option<String>,
     pub status: String,
     pub ticket: Option<String>,
 }
+
+#[derive(Serialize, Deserialize)]
+pub struct Icinga2Downtimes {
+    pub results: Vec<Icinga2DowntimeResult>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Icinga2DowntimeResult {
+    pub attrs: DowntimeResultAttrs,
+    pub name: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct DowntimeResultAttrs {
+    pub author: String,
+    pub comment: String,
+    pub duration: f64,
+    pub end_time: f64,
+    pub entry_time: f64,
+    pub fixed: bool,
+    pub host_name: String,
+    pub is_in_effect: bool,
+    pub service_name: String,
+    pub start_time: f64,
+    pub triggered_by: String,
+}
+
+// Child downtimes scheduled together with a downtime reference the parent downtime
+#[derive(Serialize, Deserialize)]
+pub struct Icinga2ChildDowntimes {
+    pub results: Vec<Icinga2ChildDowntime>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Icinga2ChildDowntime {
+    pub attrs: ChildDowntimeAttrs,
+    pub name: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ChildDowntimeAttrs {
+    #[serde(default)]
+    pub parent: String,
+    #[serde(default)]
+    pub triggered_by: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Icinga2Comments {
+    pub results: Vec<Icinga2CommentResult>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Icinga2CommentResult {
+    pub attrs: CommentResultAttrs,
+    pub name: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct CommentResultAttrs {
+    pub author: String,
+    pub entry_time: f64,
+    pub entry_type: f64,
+    pub expire_time: f64,
+    pub host_name: String,
+    pub service_name: String,
+    pub text: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Icinga2Objects {
+    pub results: Vec<Icinga2ObjectResult>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Icinga2ObjectResult {
+    pub name: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Icinga2Groups {
+    pub results: Vec<Icinga2GroupResult>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Icinga2GroupResult {
+    pub attrs: GroupResultAttrs,
+    pub name: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct GroupResultAttrs {
+    pub display_name: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Icinga2GroupMembers {
+    pub results: Vec<Icinga2GroupMemberResult>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Icinga2GroupMemberResult {
+    pub attrs: GroupMemberAttrs,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct GroupMemberAttrs {
+    pub groups: Vec<String>,
+    pub state: f64,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Icinga2ConfigPackages {
+    pub results: Vec<Icinga2ConfigPackage>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Icinga2ConfigPackage {
+    #[serde(rename = "active-stage")]
+    pub active_stage: String,
+    pub name: String,
+    pub stages: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Icinga2ConfigStageFiles {
+    pub results: Vec<Icinga2ConfigStageFile>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Icinga2ConfigStageFile {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub file_type: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Icinga2ConfigReply {
+    pub results: Vec<Icinga2ConfigReplyResult>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Icinga2ConfigReplyResult {
+    pub code: f64,
+    pub package: String,
+    pub stage: Option<String>,
+    pub status: String,
+}
+
+// Passive check result read from a file by process-check-result
+#[derive(Deserialize)]
+pub struct CheckResultRecord {
+    pub host: String,
+    #[serde(default)]
+    pub service: String,
+    pub exit_status: serde_json::Value,
+    pub output: String,
+    #[serde(default)]
+    pub perfdata: serde_json::Value,
+    #[serde(default)]
+    pub check_source: String,
+    pub ttl: Option<i64>,
+    pub execution_start: Option<i64>,
+    pub execution_end: Option<i64>,
+}
+
+// Request payloads of Icinga2 actions and object queries.
+// All values are escaped by serde_json, so user supplied text can't break the JSON payload.
+
+// Variables referenced by the filter expression, see command::filter
+pub type FilterVars = BTreeMap<String, serde_json::Value>;
+
+#[derive(Serialize)]
+pub struct Icinga2ObjectQuery {
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub filter: String,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub filter_vars: FilterVars,
+}
+
+#[derive(Serialize)]
+pub struct AddAckRequest {
+    #[serde(rename = "type")]
+    pub object_type: String,
+    pub filter: String,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub filter_vars: FilterVars,
+    pub author: String,
+    pub comment: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expiry: Option<i64>,
+    pub sticky: bool,
+    pub notify: bool,
+    pub persistent: bool,
+}
+
+#[derive(Serialize)]
+pub struct DelAckRequest {
+    #[serde(rename = "type")]
+    pub object_type: String,
+    pub filter: String,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub filter_vars: FilterVars,
+    pub author: String,
+}
+
+#[derive(Serialize)]
+pub struct AddCommentRequest {
+    #[serde(rename = "type")]
+    pub object_type: String,
+    pub filter: String,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub filter_vars: FilterVars,
+    pub author: String,
+    pub comment: String,
+}
+
+#[derive(Serialize)]
+pub struct SendCustomNotificationRequest {
+    #[serde(rename = "type")]
+    pub object_type: String,
+    pub filter: String,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub filter_vars: FilterVars,
+    pub author: String,
+    pub comment: String,
+    pub force: bool,
+}
+
+#[derive(Serialize)]
+pub struct DelayNotificationRequest {
+    #[serde(rename = "type")]
+    pub object_type: String,
+    pub filter: String,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub filter_vars: FilterVars,
+    pub timestamp: i64,
+}
+
+// Modification of the attributes of objects, restore_attrs reverts attributes to the value of
+// the configuration
+#[derive(Serialize)]
+pub struct ModifyObjectRequest {
+    pub filter: String,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub filter_vars: FilterVars,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub attrs: BTreeMap<String, serde_json::Value>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub restore_attrs: Vec<String>,
+}
+
+#[derive(Serialize)]
+pub struct DelCommentRequest {
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub comment: String,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub author: String,
+    #[serde(rename = "type", skip_serializing_if = "String::is_empty")]
+    pub object_type: String,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub filter: String,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub filter_vars: FilterVars,
+}
+
+#[derive(Serialize)]
+pub struct AddDowntimeRequest {
+    #[serde(rename = "type")]
+    pub object_type: String,
+    pub filter: String,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub filter_vars: FilterVars,
+    pub author: String,
+    pub comment: String,
+    pub start_time: i64,
+    pub end_time: i64,
+    pub fixed: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration: Option<i64>,
+    pub all_services: bool,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub trigger_name: String,
+    pub child_options: String,
+}
+
+// Files of a config stage, the key is the path of the file relative to the stage
+#[derive(Serialize)]
+pub struct ConfigStageRequest {
+    pub files: BTreeMap<String, String>,
+}
+
+#[derive(Serialize)]
+pub struct DelDowntimeRequest {
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub downtime: String,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub author: String,
+    #[serde(rename = "type", skip_serializing_if = "String::is_empty")]
+    pub object_type: String,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub filter: String,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub filter_vars: FilterVars,
+}
+
+#[derive(Serialize)]
+pub struct GenerateTicketRequest {
+    pub cn: String,
+}
+
+#[derive(Serialize)]
+pub struct RescheduleCheckRequest {
+    #[serde(rename = "type")]
+    pub object_type: String,
+    pub filter: String,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub filter_vars: FilterVars,
+    pub force: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_check: Option<i64>,
+}
+
+#[derive(Serialize)]
+pub struct ProcessCheckResultRequest {
+    #[serde(rename = "type")]
+    pub object_type: String,
+    pub filter: String,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub filter_vars: FilterVars,
+    pub exit_status: i32,
+    pub plugin_output: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub performance_data: Vec<String>,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub check_source: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ttl: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub execution_start: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub execution_end: Option<i64>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+
+    const HOSTILE: &str =
+        "say \"hi\", C:\\temp\\\nnext line\t\u{1}\"}, \"sticky\": true, \"x\": \"";
+
+    #[test]
+    fn add_ack_request_escapes_user_input() {
+        let req = AddAckRequest {
+            object_type: "Host".to_string(),
+            filter: "match(\"a\", host.name)".to_string(),
+            filter_vars: FilterVars::new(),
+            author: HOSTILE.to_string(),
+            comment: HOSTILE.to_string(),
+            expiry: None,
+            sticky: false,
+            notify: true,
+            persistent: false,
+        };
+        let payload = serde_json::to_string(&req).unwrap();
+        let parsed: Value = serde_json::from_str(&payload).unwrap();
+
+        assert_eq!(parsed["type"], "Host");
+        assert_eq!(parsed["filter"], "match(\"a\", host.name)");
+        assert_eq!(parsed["author"], HOSTILE);
+        assert_eq!(parsed["comment"], HOSTILE);
+        assert_eq!(parsed["sticky"], false);
+        assert!(parsed.get("expiry").is_none());
+        assert!(parsed.get("x").is_none());
+    }
+
+    #[test]
+    fn add_comment_request_escapes_user_input() {
+        let req = AddCommentRequest {
+            object_type: "Service".to_string(),
+            filter: String::new(),
+            filter_vars: FilterVars::new(),
+            author: "a\\".to_string(),
+            comment: HOSTILE.to_string(),
+        };
+        let payload = serde_json::to_string(&req).unwrap();
+        let parsed: Value = serde_json::from_str(&payload).unwrap();
+
+        assert_eq!(parsed["author"], "a\\");
+        assert_eq!(parsed["comment"], HOSTILE);
+        assert_eq!(parsed.as_object().unwrap().len(), 4);
+    }
+
+    #[test]
+    fn modify_object_request() {
+        let mut attrs = BTreeMap::new();
+        attrs.insert("enable_notifications".to_string(), Value::from(false));
+        let req = ModifyObjectRequest {
+            filter: "host.name == icinga2ctl_host_name".to_string(),
+            filter_vars: FilterVars::new(),
+            attrs,
+            restore_attrs: Vec::new(),
+        };
+        let payload = serde_json::to_string(&req).unwrap();
+
+        assert_eq!(
+            payload,
+            "{\"filter\":\"host.name == icinga2ctl_host_name\",\"attrs\":{\"enable_notifications\":false}}"
+        );
+
+        let req = ModifyObjectRequest {
+            filter: String::new(),
+            filter_vars: FilterVars::new(),
+            attrs: BTreeMap::new(),
+            restore_attrs: vec!["enable_flapping".to_string()],
+        };
+        let payload = serde_json::to_string(&req).unwrap();
+
+        assert_eq!(
+            payload,
+            "{\"filter\":\"\",\"restore_attrs\":[\"enable_flapping\"]}"
+        );
+    }
+
+    #[test]
+    fn send_custom_notification_request_escapes_user_input() {
+        let req = SendCustomNotificationRequest {
+            object_type: "Host".to_string(),
+            filter: String::new(),
+            filter_vars: FilterVars::new(),
+            author: "a\\".to_string(),
+            comment: HOSTILE.to_string(),
+            force: true,
+        };
+        let payload = serde_json::to_string(&req).unwrap();
+        let parsed: Value = serde_json::from_str(&payload).unwrap();
+
+        assert_eq!(parsed["comment"], HOSTILE);
+        assert_eq!(parsed["force"], true);
+        assert_eq!(parsed.as_object().unwrap().len(), 5);
+    }
+
+    #[test]
+    fn add_downtime_request_skips_unset_values() {
+        let req = AddDowntimeRequest {
+            object_type: "Host".to_string(),
+            filter: "true".to_string(),
+            filter_vars: FilterVars::new(),
+            author: "admin".to_string(),
+            comment: HOSTILE.to_string(),
+            start_time: 1600000000,
+            end_time: 1600003600,
+            fixed: true,
+            duration: None,
+            all_services: false,
+            trigger_name: String::new(),
+            child_options: "DowntimeNoChildren".to_string(),
+        };
+        let payload = serde_json::to_string(&req).unwrap();
+        let parsed: Value = serde_json::from_str(&payload).unwrap();
+
+        assert_eq!(parsed["comment"], HOSTILE);
+        assert_eq!(parsed["start_time"], 1600000000);
+        assert_eq!(parsed["end_time"], 1600003600);
+        assert!(parsed.get("duration").is_none());
+        assert!(parsed.get("trigger_name").is_none());
+    }
+
+    #[test]
+    fn del_comment_request_by_name() {
+        let req = DelCommentRequest {
+            comment: "host!svc!\"quoted\"\n".to_string(),
+            author: String::new(),
+            object_type: String::new(),
+            filter: String::new(),
+            filter_vars: FilterVars::new(),
+        };
+        let payload = serde_json::to_string(&req).unwrap();
+        let parsed: Value = serde_json::from_str(&payload).unwrap();
+
+        assert_eq!(parsed["comment"], "host!svc!\"quoted\"\n");
+        assert_eq!(parsed.as_object().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn del_downtime_request_by_filter() {
+        let req = DelDowntimeRequest {
+            downtime: String::new(),
+            author: "\\\"".to_string(),
+            object_type: "Host".to_string(),
+            filter: "match(\"h\\\\\", host.name)".to_string(),
+            filter_vars: FilterVars::new(),
+        };
+        let payload = serde_json::to_string(&req).unwrap();
+        let parsed: Value = serde_json::from_str(&payload).unwrap();
+
+        assert!(parsed.get("downtime").is_none());
+        assert_eq!(parsed["author"], "\\\"");
+        assert_eq!(parsed["type"], "Host");
+        assert_eq!(parsed["filter"], "match(\"h\\\\\", host.name)");
+    }
+
+    #[test]
+    fn generate_ticket_request_escapes_cn() {
+        let req = GenerateTicketRequest {
+            cn: HOSTILE.to_string(),
+        };
+        let payload = serde_json::to_string(&req).unwrap();
+        let parsed: Value = serde_json::from_str(&payload).unwrap();
+
+        assert_eq!(parsed["cn"], HOSTILE);
+    }
+
+    #[test]
+    fn object_query_without_filter_is_empty() {
+        let req = Icinga2ObjectQuery {
+            filter: String::new(),
+            filter_vars: FilterVars::new(),
+        };
+        assert_eq!(serde_json::to_string(&req).unwrap(), "{}");
+    }
+}
@@ -4,16 +4,33 @@ extern crate simple_error;
 mod command;
 mod configuration;
 mod constants;
+mod fanout;
 mod json_data;
+mod output;
+mod perfdata;
 mod request;
+mod time_parser;
 mod usage;
 mod util;
 
 use clap::{App, Arg, SubCommand};
+use std::env;
+use std::path::Path;
 use std::process;
 
 fn main() {
     let mut config_file = String::new();
+    let mut instance = String::new();
+
+    let override_args: Vec<Arg> = constants::CONFIG_OVERRIDES
+        .iter()
+        .map(|(key, opt, _)| {
+            Arg::with_name(key)
+                .help("Override configuration value")
+                .long(opt)
+                .takes_value(true)
+        })
+        .collect();
 
     let options = App::new(constants::NAME)
         .version(constants::VERSION)
@@ -36,7 +53,27 @@ fn main() {
                 .help("Show debug information")
                 .long("debug")
                 .short("-d"),
+            Arg::with_name("dry_run")
+                .help("Show affected objects and payload but don't run the action")
+                .long("dry-run"),
+            Arg::with_name("force_many")
+                .help("Run actions without confirmation, even if more than max_objects objects are affected")
+                .long("force-many"),
+            Arg::with_name("output")
+                .help("Output format")
+                .long("output")
+                .takes_value(true),
+            Arg::with_name("instance")
+                .help("Use Icinga2 instance <instance> from configuration file")
+                .short("i")
+                .long("instance")
+                .takes_value(true),
+            Arg::with_name("all_instances")
+                .help("Use all Icinga2 instances from configuration file")
+                .long("all-instances")
+                .conflicts_with("instance"),
         ])
+        .args(&override_args)
         .subcommand(
             SubCommand::with_name("add-ack")
                 .about("Add acknowledgement")
@@ -51,6 +88,18 @@ fn main() {
                         .short("S")
                         .long("service")
                         .takes_value(true),
+                    Arg::with_name("hostgroup")
+                        .help("Select hosts in host group <hostgroup>")
+                        .long("hostgroup")
+                        .takes_value(true),
+                    Arg::with_name("servicegroup")
+                        .help("Select services in service group <servicegroup>")
+                        .long("servicegroup")
+                        .takes_value(true),
+                    Arg::with_name("filter")
+                        .help("Additional Icinga2 filter expression")
+                        .long("filter")
+                        .takes_value(true),
                     Arg::with_name("author")
                         .help("Acknowledgment author")
                         .short("a")
@@ -110,6 +159,18 @@ fn main() {
                         .short("S")
                         .long("service")
                         .takes_value(true),
+                    Arg::with_name("hostgroup")
+                        .help("Select hosts in host group <hostgroup>")
+                        .long("hostgroup")
+                        .takes_value(true),
+                    Arg::with_name("servicegroup")
+                        .help("Select services in service group <servicegroup>")
+                        .long("servicegroup")
+                        .takes_value(true),
+                    Arg::with_name("filter")
+                        .help("Additional Icinga2 filter expression")
+                        .long("filter")
+                        .takes_value(true),
                     Arg::with_name("author")
                         .help("Downtime author")
                         .short("a")
@@ -140,6 +201,18 @@ fn main() {
                         .short("S")
                         .long("service")
                         .takes_value(true),
+                    Arg::with_name("hostgroup")
+                        .help("Select hosts in host group <hostgroup>")
+                        .long("hostgroup")
+                        .takes_value(true),
+                    Arg::with_name("servicegroup")
+                        .help("Select services in service group <servicegroup>")
+                        .long("servicegroup")
+                        .takes_value(true),
+                    Arg::with_name("filter")
+                        .help("Additional Icinga2 filter expression")
+                        .long("filter")
+                        .takes_value(true),
                     Arg::with_name("author")
                         .help("Downtime author")
                         .short("a")
@@ -189,6 +262,15 @@ fn main() {
                         .takes_value(true),
                 ]),
         )
+        .subcommand(
+            SubCommand::with_name("config")
+                .about("Show configuration")
+                .args(&[Arg::with_name("help")
+                    .help("Show this text")
+                    .short("h")
+                    .long("help")])
+                .subcommand(SubCommand::with_name("list").about("List defined Icinga2 instances")),
+        )
         .subcommand(
             SubCommand::with_name("del-ack")
                 .about("Remove acknowledgement")
@@ -203,6 +285,18 @@ fn main() {
                         .short("S")
                         .long("service")
                         .takes_value(true),
+                    Arg::with_name("hostgroup")
+                        .help("Select hosts in host group <hostgroup>")
+                        .long("hostgroup")
+                        .takes_value(true),
+                    Arg::with_name("servicegroup")
+                        .help("Select services in service group <servicegroup>")
+                        .long("servicegroup")
+                        .takes_value(true),
+                    Arg::with_name("filter")
+                        .help("Additional Icinga2 filter expression")
+                        .long("filter")
+                        .takes_value(true),
                     Arg::with_name("author")
                         .help("Acknowledgment author")
                         .short("a")
@@ -240,6 +334,18 @@ fn main() {
                         .short("S")
                         .long("service")
                         .takes_value(true),
+                    Arg::with_name("hostgroup")
+                        .help("Select hosts in host group <hostgroup>")
+                        .long("hostgroup")
+                        .takes_value(true),
+                    Arg::with_name("servicegroup")
+                        .help("Select services in service group <servicegroup>")
+                        .long("servicegroup")
+                        .takes_value(true),
+                    Arg::with_name("filter")
+                        .help("Additional Icinga2 filter expression")
+                        .long("filter")
+                        .takes_value(true),
                     Arg::with_name("author")
                         .help("Comment removal author")
                         .short("a")
@@ -270,6 +376,18 @@ fn main() {
                         .short("S")
                         .long("service")
                         .takes_value(true),
+                    Arg::with_name("hostgroup")
+                        .help("Select hosts in host group <hostgroup>")
+                        .long("hostgroup")
+                        .takes_value(true),
+                    Arg::with_name("servicegroup")
+                        .help("Select services in service group <servicegroup>")
+                        .long("servicegroup")
+                        .takes_value(true),
+                    Arg::with_name("filter")
+                        .help("Additional Icinga2 filter expression")
+                        .long("filter")
+                        .takes_value(true),
                     Arg::with_name("author")
                         .help("Downtime removal author")
                         .short("a")
@@ -284,6 +402,170 @@ fn main() {
                         .short("D")
                         .long("downtime")
                         .takes_value(true),
+                    Arg::with_name("created_by")
+                        .help("Remove downtimes created by <author>")
+                        .long("created-by")
+                        .takes_value(true),
+                    Arg::with_name("mine")
+                        .help("Remove downtimes created by the current user")
+                        .long("mine"),
+                    Arg::with_name("comment")
+                        .help("Remove downtimes with a comment matching the regular expression <regex>")
+                        .long("comment")
+                        .takes_value(true),
+                    Arg::with_name("expires_before")
+                        .help("Remove downtimes ending before <time>")
+                        .long("expires-before")
+                        .takes_value(true),
+                    Arg::with_name("expires_after")
+                        .help("Remove downtimes ending after <time>")
+                        .long("expires-after")
+                        .takes_value(true),
+                    Arg::with_name("created_before")
+                        .help("Remove downtimes created before <time>")
+                        .long("created-before")
+                        .takes_value(true),
+                    Arg::with_name("created_after")
+                        .help("Remove downtimes created at or after <time>")
+                        .long("created-after")
+                        .takes_value(true),
+                ]),
+        )
+        .subcommand(
+            SubCommand::with_name("delay-notification")
+                .about("Delay notifications")
+                .args(&[
+                    Arg::with_name("host_object")
+                        .help("Delay notifications for host <host_object>")
+                        .short("H")
+                        .long("host")
+                        .takes_value(true),
+                    Arg::with_name("service_object")
+                        .help("Delay notifications for service <service_object>")
+                        .short("S")
+                        .long("service")
+                        .takes_value(true),
+                    Arg::with_name("hostgroup")
+                        .help("Select hosts in host group <hostgroup>")
+                        .long("hostgroup")
+                        .takes_value(true),
+                    Arg::with_name("servicegroup")
+                        .help("Select services in service group <servicegroup>")
+                        .long("servicegroup")
+                        .takes_value(true),
+                    Arg::with_name("filter")
+                        .help("Additional Icinga2 filter expression")
+                        .long("filter")
+                        .takes_value(true),
+                    Arg::with_name("until")
+                        .help("Delay notifications until <time>")
+                        .short("t")
+                        .long("until")
+                        .takes_value(true),
+                    Arg::with_name("warning")
+                        .help("Delay notifications for host/services with WARNING state")
+                        .short("w")
+                        .long("warning"),
+                    Arg::with_name("critical")
+                        .help("Delay notifications for host/services with CRITICAL state")
+                        .short("c")
+                        .long("critical"),
+                    Arg::with_name("unknown")
+                        .help("Delay notifications for host/services with UNKNOWN state")
+                        .short("u")
+                        .long("unknown"),
+                    Arg::with_name("help")
+                        .help("Show this text")
+                        .short("h")
+                        .long("help"),
+                ]),
+        )
+        .subcommand(
+            SubCommand::with_name("disable-notifications")
+                .about("Disable notifications")
+                .args(&[
+                    Arg::with_name("host_object")
+                        .help("Disable notifications for host <host_object>")
+                        .short("H")
+                        .long("host")
+                        .takes_value(true),
+                    Arg::with_name("service_object")
+                        .help("Disable notifications for service <service_object>")
+                        .short("S")
+                        .long("service")
+                        .takes_value(true),
+                    Arg::with_name("hostgroup")
+                        .help("Select hosts in host group <hostgroup>")
+                        .long("hostgroup")
+                        .takes_value(true),
+                    Arg::with_name("servicegroup")
+                        .help("Select services in service group <servicegroup>")
+                        .long("servicegroup")
+                        .takes_value(true),
+                    Arg::with_name("filter")
+                        .help("Additional Icinga2 filter expression")
+                        .long("filter")
+                        .takes_value(true),
+                    Arg::with_name("warning")
+                        .help("Disable notifications for host/services with WARNING state")
+                        .short("w")
+                        .long("warning"),
+                    Arg::with_name("critical")
+                        .help("Disable notifications for host/services with CRITICAL state")
+                        .short("c")
+                        .long("critical"),
+                    Arg::with_name("unknown")
+                        .help("Disable notifications for host/services with UNKNOWN state")
+                        .short("u")
+                        .long("unknown"),
+                    Arg::with_name("help")
+                        .help("Show this text")
+                        .short("h")
+                        .long("help"),
+                ]),
+        )
+        .subcommand(
+            SubCommand::with_name("enable-notifications")
+                .about("Enable notifications")
+                .args(&[
+                    Arg::with_name("host_object")
+                        .help("Enable notifications for host <host_object>")
+                        .short("H")
+                        .long("host")
+                        .takes_value(true),
+                    Arg::with_name("service_object")
+                        .help("Enable notifications for service <service_object>")
+                        .short("S")
+                        .long("service")
+                        .takes_value(true),
+                    Arg::with_name("hostgroup")
+                        .help("Select hosts in host group <hostgroup>")
+                        .long("hostgroup")
+                        .takes_value(true),
+                    Arg::with_name("servicegroup")
+                        .help("Select services in service group <servicegroup>")
+                        .long("servicegroup")
+                        .takes_value(true),
+                    Arg::with_name("filter")
+                        .help("Additional Icinga2 filter expression")
+                        .long("filter")
+                        .takes_value(true),
+                    Arg::with_name("warning")
+                        .help("Enable notifications for host/services with WARNING state")
+                        .short("w")
+                        .long("warning"),
+                    Arg::with_name("critical")
+                        .help("Enable notifications for host/services with CRITICAL state")
+                        .short("c")
+                        .long("critical"),
+                    Arg::with_name("unknown")
+                        .help("Enable notifications for host/services with UNKNOWN state")
+                        .short("u")
+                        .long("unknown"),
+                    Arg::with_name("help")
+                        .help("Show this text")
+                        .short("h")
+                        .long("help"),
                 ]),
         )
         .subcommand(
@@ -301,6 +583,262 @@ fn main() {
                         .long("help"),
                 ]),
         )
+        .subcommand(
+            SubCommand::with_name("list-acks")
+                .about("List acknowledgements")
+                .args(&[
+                    Arg::with_name("host_object")
+                        .help("List acknowledgements of host <host_object>")
+                        .short("H")
+                        .long("host")
+                        .takes_value(true),
+                    Arg::with_name("service_object")
+                        .help("List acknowledgements of service <service_object>")
+                        .short("S")
+                        .long("service")
+                        .takes_value(true),
+                    Arg::with_name("filter")
+                        .help("Additional Icinga2 filter expression")
+                        .long("filter")
+                        .takes_value(true),
+                    Arg::with_name("author")
+                        .help("List acknowledgements created by <author>")
+                        .short("a")
+                        .long("author")
+                        .takes_value(true),
+                    Arg::with_name("help")
+                        .help("Show this text")
+                        .short("h")
+                        .long("help"),
+                ]),
+        )
+        .subcommand(
+            SubCommand::with_name("list-comments")
+                .about("List comments")
+                .args(&[
+                    Arg::with_name("host_object")
+                        .help("List comments of host <host_object>")
+                        .short("H")
+                        .long("host")
+                        .takes_value(true),
+                    Arg::with_name("service_object")
+                        .help("List comments of service <service_object>")
+                        .short("S")
+                        .long("service")
+                        .takes_value(true),
+                    Arg::with_name("filter")
+                        .help("Additional Icinga2 filter expression")
+                        .long("filter")
+                        .takes_value(true),
+                    Arg::with_name("author")
+                        .help("List comments created by <author>")
+                        .short("a")
+                        .long("author")
+                        .takes_value(true),
+                    Arg::with_name("type")
+                        .help("List comments of type <type>")
+                        .short("t")
+                        .long("type")
+                        .takes_value(true),
+                    Arg::with_name("help")
+                        .help("Show this text")
+                        .short("h")
+                        .long("help"),
+                ]),
+        )
+        .subcommand(
+            SubCommand::with_name("list-downtimes")
+                .about("List downtimes")
+                .args(&[
+                    Arg::with_name("host_object")
+                        .help("List downtimes of host <host_object>")
+                        .short("H")
+                        .long("host")
+                        .takes_value(true),
+                    Arg::with_name("service_object")
+                        .help("List downtimes of service <service_object>")
+                        .short("S")
+                        .long("service")
+                        .takes_value(true),
+                    Arg::with_name("filter")
+                        .help("Additional Icinga2 filter expression")
+                        .long("filter")
+                        .takes_value(true),
+                    Arg::with_name("author")
+                        .help("List downtimes created by <author>")
+                        .short("a")
+                        .long("author")
+                        .takes_value(true),
+                    Arg::with_name("active")
+                        .help("List downtimes currently in effect")
+                        .short("A")
+                        .long("active"),
+                    Arg::with_name("fixed")
+                        .help("List fixed downtimes")
+                        .short("f")
+                        .long("fixed"),
+                    Arg::with_name("flexible")
+                        .help("List flexible downtimes")
+                        .short("F")
+                        .long("flexible"),
+                    Arg::with_name("start")
+                        .help("List downtimes ending after <time>")
+                        .short("s")
+                        .long("start")
+                        .takes_value(true),
+                    Arg::with_name("end")
+                        .help("List downtimes starting before <time>")
+                        .short("e")
+                        .long("end")
+                        .takes_value(true),
+                    Arg::with_name("help")
+                        .help("Show this text")
+                        .short("h")
+                        .long("help"),
+                ]),
+        )
+        .subcommand(
+            SubCommand::with_name("list-groups")
+                .about("List host and service groups")
+                .args(&[
+                    Arg::with_name("group")
+                        .help("List groups matching <group>")
+                        .short("g")
+                        .long("group")
+                        .takes_value(true),
+                    Arg::with_name("type")
+                        .help("List groups of type <type>")
+                        .short("t")
+                        .long("type")
+                        .takes_value(true),
+                    Arg::with_name("help")
+                        .help("Show this text")
+                        .short("h")
+                        .long("help"),
+                ]),
+        )
+        .subcommand(
+            SubCommand::with_name("modify-downtime")
+                .about("Change the end time or duration of downtimes")
+                .args(&[
+                    Arg::with_name("host_object")
+                        .help("Modify downtimes of host <host_object>")
+                        .short("H")
+                        .long("host")
+                        .takes_value(true),
+                    Arg::with_name("service_object")
+                        .help("Modify downtimes of service <service_object>")
+                        .short("S")
+                        .long("service")
+                        .takes_value(true),
+                    Arg::with_name("hostgroup")
+                        .help("Select hosts in host group <hostgroup>")
+                        .long("hostgroup")
+                        .takes_value(true),
+                    Arg::with_name("servicegroup")
+                        .help("Select services in service group <servicegroup>")
+                        .long("servicegroup")
+                        .takes_value(true),
+                    Arg::with_name("filter")
+                        .help("Additional Icinga2 filter expression")
+                        .long("filter")
+                        .takes_value(true),
+                    Arg::with_name("downtime_name")
+                        .help("Name of downtime to modify")
+                        .short("D")
+                        .long("downtime")
+                        .takes_value(true),
+                    Arg::with_name("author")
+                        .help("Author of the modified downtime")
+                        .short("a")
+                        .long("author")
+                        .takes_value(true),
+                    Arg::with_name("end")
+                        .help("Set new end time of the downtime")
+                        .short("e")
+                        .long("end")
+                        .takes_value(true),
+                    Arg::with_name("extend")
+                        .help("Extend the downtime by <duration>")
+                        .short("x")
+                        .long("extend")
+                        .takes_value(true),
+                    Arg::with_name("duration")
+                        .help("Set new duration of the downtime")
+                        .short("d")
+                        .long("duration")
+                        .takes_value(true),
+                    Arg::with_name("help")
+                        .help("Show this text")
+                        .short("h")
+                        .long("help"),
+                ]),
+        )
+        .subcommand(
+            SubCommand::with_name("process-check-result")
+                .about("Submit passive check result")
+                .args(&[
+                    Arg::with_name("host_object")
+                        .help("Submit check result for host <host_object>")
+                        .short("H")
+                        .long("host")
+                        .takes_value(true),
+                    Arg::with_name("service_object")
+                        .help("Submit check result for service <service_object>")
+                        .short("S")
+                        .long("service")
+                        .takes_value(true),
+                    Arg::with_name("exit_status")
+                        .help("Exit status of the check")
+                        .short("s")
+                        .long("status")
+                        .takes_value(true),
+                    Arg::with_name("output")
+                        .help("Plugin output")
+                        .short("o")
+                        .long("output")
+                        .takes_value(true),
+                    Arg::with_name("perfdata")
+                        .help("Performance data")
+                        .short("p")
+                        .long("perfdata")
+                        .takes_value(true)
+                        .multiple(true)
+                        .number_of_values(1),
+                    Arg::with_name("check_source")
+                        .help("Source of the check result")
+                        .short("c")
+                        .long("check-source")
+                        .takes_value(true),
+                    Arg::with_name("ttl")
+                        .help("Time to live of the check result")
+                        .short("t")
+                        .long("ttl")
+                        .takes_value(true),
+                    Arg::with_name("execution_start")
+                        .help("Start time of the check execution")
+                        .long("execution-start")
+                        .takes_value(true),
+                    Arg::with_name("execution_end")
+                        .help("End time of the check execution")
+                        .long("execution-end")
+                        .takes_value(true),
+                    Arg::with_name("from_file")
+                        .help("Read check results from <file>")
+                        .short("f")
+                        .long("from-file")
+                        .takes_value(true),
+                    Arg::with_name("parallel")
+                        .help("Number of concurrent requests")
+                        .short("P")
+                        .long("parallel")
+                        .takes_value(true),
+                    Arg::with_name("help")
+                        .help("Show this text")
+                        .short("h")
+                        .long("help"),
+                ]),
+        )
         .subcommand(
             SubCommand::with_name("reschedule-check")
                 .about("Reschedule checks of host and service objects")
@@ -315,6 +853,18 @@ fn main() {
                         .short("S")
                         .long("service")
                         .takes_value(true),
+                    Arg::with_name("hostgroup")
+                        .help("Select hosts in host group <hostgroup>")
+                        .long("hostgroup")
+                        .takes_value(true),
+                    Arg::with_name("servicegroup")
+                        .help("Select services in service group <servicegroup>")
+                        .long("servicegroup")
+                        .takes_value(true),
+                    Arg::with_name("filter")
+                        .help("Additional Icinga2 filter expression")
+                        .long("filter")
+                        .takes_value(true),
                     Arg::with_name("help")
                         .help("Show this text")
                         .short("h")
@@ -351,6 +901,181 @@ fn main() {
                         .takes_value(true),
                 ]),
         )
+        .subcommand(
+            SubCommand::with_name("schedule-downtime")
+                .about("Schedule recurring downtimes")
+                .args(&[
+                    Arg::with_name("host_object")
+                        .help("Schedule downtime for host <host_object>")
+                        .short("H")
+                        .long("host")
+                        .takes_value(true),
+                    Arg::with_name("service_object")
+                        .help("Schedule downtime for service <service_object>")
+                        .short("S")
+                        .long("service")
+                        .takes_value(true),
+                    Arg::with_name("hostgroup")
+                        .help("Select hosts in host group <hostgroup>")
+                        .long("hostgroup")
+                        .takes_value(true),
+                    Arg::with_name("servicegroup")
+                        .help("Select services in service group <servicegroup>")
+                        .long("servicegroup")
+                        .takes_value(true),
+                    Arg::with_name("filter")
+                        .help("Additional Icinga2 filter expression")
+                        .long("filter")
+                        .takes_value(true),
+                    Arg::with_name("author")
+                        .help("Downtime author")
+                        .short("a")
+                        .long("author")
+                        .takes_value(true),
+                    Arg::with_name("comment")
+                        .help("Comment to add")
+                        .short("C")
+                        .long("comment")
+                        .takes_value(true),
+                    Arg::with_name("help")
+                        .help("Show this text")
+                        .short("h")
+                        .long("help"),
+                    Arg::with_name("duration")
+                        .help("Set downtime duration for flexible downtime")
+                        .short("d")
+                        .long("duration")
+                        .takes_value(true),
+                    Arg::with_name("child_opts")
+                        .help("Schedule child downtime")
+                        .short("c")
+                        .long("child")
+                        .takes_value(true),
+                    Arg::with_name("fixed")
+                        .help("Add fixed downtime instead of a flexible downtime")
+                        .short("f")
+                        .long("fixed"),
+                    Arg::with_name("range")
+                        .help("Time range of the downtime, e.g. monday=02:00-04:00")
+                        .short("r")
+                        .long("range")
+                        .takes_value(true)
+                        .multiple(true)
+                        .number_of_values(1),
+                    Arg::with_name("count")
+                        .help("Number of downtimes to schedule")
+                        .short("n")
+                        .long("count")
+                        .takes_value(true),
+                    Arg::with_name("config")
+                        .help("Create a ScheduledDowntime object instead of downtimes")
+                        .long("config"),
+                    Arg::with_name("name")
+                        .help("Name of the ScheduledDowntime object")
+                        .short("N")
+                        .long("name")
+                        .takes_value(true),
+                ]),
+        )
+        .subcommand(
+            SubCommand::with_name("send-custom-notification")
+                .about("Send a custom notification")
+                .args(&[
+                    Arg::with_name("host_object")
+                        .help("Send notification for host <host_object>")
+                        .short("H")
+                        .long("host")
+                        .takes_value(true),
+                    Arg::with_name("service_object")
+                        .help("Send notification for service <service_object>")
+                        .short("S")
+                        .long("service")
+                        .takes_value(true),
+                    Arg::with_name("hostgroup")
+                        .help("Select hosts in host group <hostgroup>")
+                        .long("hostgroup")
+                        .takes_value(true),
+                    Arg::with_name("servicegroup")
+                        .help("Select services in service group <servicegroup>")
+                        .long("servicegroup")
+                        .takes_value(true),
+                    Arg::with_name("filter")
+                        .help("Additional Icinga2 filter expression")
+                        .long("filter")
+                        .takes_value(true),
+                    Arg::with_name("author")
+                        .help("Notification author")
+                        .short("a")
+                        .long("author")
+                        .takes_value(true),
+                    Arg::with_name("comment")
+                        .help("Notification comment")
+                        .short("C")
+                        .long("comment")
+                        .takes_value(true),
+                    Arg::with_name("force")
+                        .help("Send notification regardless of downtimes and notification filters")
+                        .short("f")
+                        .long("force"),
+                    Arg::with_name("help")
+                        .help("Show this text")
+                        .short("h")
+                        .long("help"),
+                ]),
+        )
+        .subcommand(
+            SubCommand::with_name("set-attr")
+                .about("Enable, disable or revert object attributes")
+                .args(&[
+                    Arg::with_name("host_object")
+                        .help("Set attributes of host <host_object>")
+                        .short("H")
+                        .long("host")
+                        .takes_value(true),
+                    Arg::with_name("service_object")
+                        .help("Set attributes of service <service_object>")
+                        .short("S")
+                        .long("service")
+                        .takes_value(true),
+                    Arg::with_name("hostgroup")
+                        .help("Select hosts in host group <hostgroup>")
+                        .long("hostgroup")
+                        .takes_value(true),
+                    Arg::with_name("servicegroup")
+                        .help("Select services in service group <servicegroup>")
+                        .long("servicegroup")
+                        .takes_value(true),
+                    Arg::with_name("filter")
+                        .help("Additional Icinga2 filter expression")
+                        .long("filter")
+                        .takes_value(true),
+                    Arg::with_name("enable")
+                        .help("Enable <attribute>")
+                        .short("e")
+                        .long("enable")
+                        .takes_value(true)
+                        .multiple(true)
+                        .number_of_values(1),
+                    Arg::with_name("disable")
+                        .help("Disable <attribute>")
+                        .short("d")
+                        .long("disable")
+                        .takes_value(true)
+                        .multiple(true)
+                        .number_of_values(1),
+                    Arg::with_name("revert")
+                        .help("Revert <attribute> to the value of the configuration")
+                        .short("r")
+                        .long("revert")
+                        .takes_value(true)
+                        .multiple(true)
+                        .number_of_values(1),
+                    Arg::with_name("help")
+                        .help("Show this text")
+                        .short("h")
+                        .long("help"),
+                ]),
+        )
         .subcommand(
             SubCommand::with_name("status")
                 .about("Show status of host and service objects")
@@ -365,6 +1090,18 @@ fn main() {
                         .short("S")
                         .long("service")
                         .takes_value(true),
+                    Arg::with_name("hostgroup")
+                        .help("Select hosts in host group <hostgroup>")
+                        .long("hostgroup")
+                        .takes_value(true),
+                    Arg::with_name("servicegroup")
+                        .help("Select services in service group <servicegroup>")
+                        .long("servicegroup")
+                        .takes_value(true),
+                    Arg::with_name("filter")
+                        .help("Additional Icinga2 filter expression")
+                        .long("filter")
+                        .takes_value(true),
                     Arg::with_name("help")
                         .help("Show this text")
                         .short("h")
@@ -399,6 +1136,10 @@ fn main() {
                         .long("state-type")
                         .short("s")
                         .takes_value(true),
+                    Arg::with_name("check")
+                        .help("Summarise status as monitoring plugin output")
+                        .long("check")
+                        .short("C"),
                 ]),
         )
         .get_matches();
@@ -441,31 +1182,147 @@ fn main() {
         config_file = v.to_string();
     }
 
+    // Without a user configuration file, the configuration must be provided by command line
+    // options and environment variables
     if config_file.is_empty() {
-        config_file = match configuration::get_default_user_config_file() {
+        if let Ok(v) = configuration::get_default_user_config_file() {
+            if Path::new(&v).exists() {
+                config_file = v;
+            }
+        }
+    }
+
+    let config_name = if config_file.is_empty() {
+        "from command line and environment"
+    } else {
+        config_file.as_str()
+    };
+
+    // Precedence: command line options > environment variables > configuration file
+    let mut overrides = Vec::new();
+    for (key, _, env_name) in constants::CONFIG_OVERRIDES {
+        if let Ok(v) = env::var(env_name) {
+            overrides.push((key.to_string(), v));
+        }
+    }
+    for (key, _, _) in constants::CONFIG_OVERRIDES {
+        if let Some(v) = options.value_of(key) {
+            overrides.push((key.to_string(), v.to_string()));
+        }
+    }
+
+    // a monitoring plugin must report all errors as UNKNOWN
+    let check_mode = matches!(options.subcommand(), ("status", Some(m)) if m.is_present("check"));
+
+    let mut output_format = constants::OUTPUT_TEXT;
+    if let Some(v) = options.value_of("output") {
+        output_format = match output::parse_format(v) {
             Ok(v) => v,
             Err(e) => {
-                eprintln!("{}", e);
+                if check_mode {
+                    println!("{} UNKNOWN - {}", constants::NAME.to_uppercase(), e);
+                    process::exit(constants::PLUGIN_UNKNOWN);
+                }
+                eprintln!("Error: {}", e);
                 process::exit(1);
             }
         };
     }
 
-    let mut config = match configuration::get_configuration(config_file.as_str()) {
-        Ok(v) => v,
-        Err(e) => {
-            eprintln!(
-                "Error: Can't parse configuration file {}: {}",
-                config_file, e
-            );
+    if let ("config", Some(m)) = options.subcommand() {
+        if let Err(e) = command::config::run(&config_file, &overrides, output_format, m) {
+            println!("Error: {}", e);
             process::exit(1);
         }
-    };
+        process::exit(0);
+    }
+
+    if let Some(v) = options.value_of("instance") {
+        instance = v.to_string();
+    }
+
+    if instance.is_empty() {
+        if let Ok(v) = env::var(constants::ENV_INSTANCE) {
+            instance = v;
+        }
+    }
+
+    let instances: Vec<String> = instance
+        .split(',')
+        .map(|i| i.trim().to_string())
+        .filter(|i| !i.is_empty())
+        .collect();
+
+    if options.is_present("all_instances") || instances.len() > 1 {
+        let names = if options.is_present("all_instances") {
+            Vec::new()
+        } else {
+            instances
+        };
+
+        let mut configs =
+            match configuration::get_configurations(config_file.as_str(), &names, &overrides) {
+                Ok(v) => v,
+                Err(e) => {
+                    if check_mode {
+                        println!(
+                            "{} UNKNOWN - Can't parse configuration {}: {}",
+                            constants::NAME.to_uppercase(),
+                            config_name,
+                            e
+                        );
+                        process::exit(constants::PLUGIN_UNKNOWN);
+                    }
+                    eprintln!("Error: Can't parse configuration {}: {}", config_name, e);
+                    process::exit(1);
+                }
+            };
+
+        for cfg in configs.iter_mut() {
+            cfg.debug = options.is_present("debug");
+            cfg.dry_run = options.is_present("dry_run");
+            cfg.force_many = options.is_present("force_many");
+            cfg.output = output_format;
+            cfg.show_instance = true;
+        }
+
+        if let (cmd, Some(m)) = options.subcommand() {
+            process::exit(fanout::run_command(&configs, cmd, m));
+        }
+    }
+
+    let mut config =
+        match configuration::get_configuration(config_file.as_str(), &instance, &overrides) {
+            Ok(v) => v,
+            Err(e) => {
+                if check_mode {
+                    println!(
+                        "{} UNKNOWN - Can't parse configuration {}: {}",
+                        constants::NAME.to_uppercase(),
+                        config_name,
+                        e
+                    );
+                    process::exit(constants::PLUGIN_UNKNOWN);
+                }
+                eprintln!("Error: Can't parse configuration {}: {}", config_name, e);
+                process::exit(1);
+            }
+        };
 
     if options.is_present("debug") {
         config.debug = true;
     }
 
+    if options.is_present("dry_run") {
+        config.dry_run = true;
+    }
+
+    if options.is_present("force_many") {
+        config.force_many = true;
+    }
+
+    config.output = output_format;
+
     match options.subcommand() {
         ("add-ack", Some(m)) => {
             if let Err(e) = command::add_ack::run(&config, &m) {
@@ -503,24 +1360,103 @@ fn main() {
                 process::exit(1);
             }
         }
+        ("delay-notification", Some(m)) => {
+            if let Err(e) = command::delay_notification::run(&config, m) {
+                println!("Error: {}", e);
+                process::exit(1);
+            }
+        }
+        ("disable-notifications", Some(m)) => {
+            if let Err(e) = command::notifications::disable(&config, m) {
+                println!("Error: {}", e);
+                process::exit(1);
+            }
+        }
+        ("enable-notifications", Some(m)) => {
+            if let Err(e) = command::notifications::enable(&config, m) {
+                println!("Error: {}", e);
+                process::exit(1);
+            }
+        }
         ("generate-ticket", Some(m)) => {
             if let Err(e) = command::generate_ticket::run(&config, &m) {
                 println!("Error: {}", e);
                 process::exit(1);
             }
         }
+        ("list-acks", Some(m)) => {
+            if let Err(e) = command::list_acks::run(&config, m) {
+                println!("Error: {}", e);
+                process::exit(1);
+            }
+        }
+        ("list-comments", Some(m)) => {
+            if let Err(e) = command::list_comments::run(&config, m) {
+                println!("Error: {}", e);
+                process::exit(1);
+            }
+        }
+        ("list-downtimes", Some(m)) => {
+            if let Err(e) = command::list_downtimes::run(&config, m) {
+                println!("Error: {}", e);
+                process::exit(1);
+            }
+        }
+        ("list-groups", Some(m)) => {
+            if let Err(e) = command::list_groups::run(&config, m) {
+                println!("Error: {}", e);
+                process::exit(1);
+            }
+        }
+        ("modify-downtime", Some(m)) => {
+            if let Err(e) = command::modify_downtime::run(&config, m) {
+                println!("Error: {}", e);
+                process::exit(1);
+            }
+        }
+        ("process-check-result", Some(m)) => {
+            if let Err(e) = command::process_check_result::run(&config, m) {
+                println!("Error: {}", e);
+                process::exit(1);
+            }
+        }
         ("reschedule-check", Some(m)) => {
             if let Err(e) = command::reschedule_check::run(&config, &m) {
                 println!("Error: {}", e);
                 process::exit(1);
             }
         }
-        ("status", Some(m)) => {
-            if let Err(e) = command::status::run(&config, &m) {
+        ("schedule-downtime", Some(m)) => {
+            if let Err(e) = command::schedule_downtime::run(&config, m) {
+                println!("Error: {}", e);
+                process::exit(1);
+            }
+        }
+        ("send-custom-notification", Some(m)) => {
+            if let Err(e) = command::send_custom_notification::run(&config, m) {
+                println!("Error: {}", e);
+                process::exit(1);
+            }
+        }
+        ("set-attr", Some(m)) => {
+            if let Err(e) = command::set_attr::run(&config, m) {
                 println!("Error: {}", e);
                 process::exit(1);
             }
         }
+        ("status", Some(m)) => match command::status::run(&config, &m) {
+            Ok(v) => {
+                process::exit(v);
+            }
+            Err(e) => {
+                if m.is_present("check") {
+                    println!("{} UNKNOWN - {}", constants::NAME.to_uppercase(), e);
+                    process::exit(constants::PLUGIN_UNKNOWN);
+                }
+                println!("Error: {}", e);
+                process::exit(1);
+            }
+        },
         _ => {
             eprintln!("Error: No command provided or unknown command");
             usage::show();
@@ -0,0 +1,112 @@
+use crate::constants;
+
+use serde::Serialize;
+use std::error::Error;
+use std::io;
+
+#[derive(Serialize)]
+pub struct StatusRecord {
+    pub instance: String,
+    pub host: String,
+    pub service: String,
+    pub state: String,
+    pub state_type: String,
+    pub acknowledgement: String,
+    pub output: String,
+    pub downtime: bool,
+}
+
+#[derive(Serialize)]
+pub struct DowntimeRecord {
+    pub instance: String,
+    pub name: String,
+    pub host: String,
+    pub service: String,
+    pub author: String,
+    pub start: String,
+    pub end: String,
+    pub duration: i64,
+    pub fixed: bool,
+    pub active: bool,
+    pub comment: String,
+    pub trigger: String,
+}
+
+#[derive(Serialize)]
+pub struct CommentRecord {
+    pub instance: String,
+    pub name: String,
+    pub host: String,
+    pub service: String,
+    pub author: String,
+    pub entry_type: String,
+    pub timestamp: String,
+    pub expire: String,
+    pub text: String,
+}
+
+#[derive(Serialize)]
+pub struct GroupRecord {
+    pub instance: String,
+    pub group_type: String,
+    pub name: String,
+    pub display_name: String,
+    pub members: u64,
+    pub problems: u64,
+    pub worst_state: String,
+}
+
+#[derive(Serialize)]
+pub struct InstanceRecord {
+    pub name: String,
+    pub default: bool,
+    pub url: String,
+    pub auth: String,
+}
+
+pub fn parse_format(f: &str) -> Result<u8, Box<dyn Error>> {
+    match f.to_lowercase().as_str() {
+        "text" => Ok(constants::OUTPUT_TEXT),
+        "json" => Ok(constants::OUTPUT_JSON),
+        "yaml" => Ok(constants::OUTPUT_YAML),
+        "csv" => Ok(constants::OUTPUT_CSV),
+        "tsv" => Ok(constants::OUTPUT_TSV),
+        _ => {
+            bail!("Invalid output format {}", f);
+        }
+    }
+}
+
+// Note: Plain text output differs for every command, so it must be handled by the command itself
+pub fn print_records<T: Serialize>(records: &[T], fmt: u8) -> Result<(), Box<dyn Error>> {
+    match fmt {
+        constants::OUTPUT_JSON => {
+            println!("{}", serde_json::to_string_pretty(records)?);
+        }
+        constants::OUTPUT_YAML => {
+            println!("{}", serde_yaml::to_string(records)?);
+        }
+        constants::OUTPUT_CSV => {
+            print_delimited(records, b',')?;
+        }
+        constants::OUTPUT_TSV => {
+            print_delimited(records, b'\t')?;
+        }
+        _ => {
+            bail!("BUG: Output format can't be serialised");
+        }
+    };
+    Ok(())
+}
+
+fn print_delimited<T: Serialize>(records: &[T], delimiter: u8) -> Result<(), Box<dyn Error>> {
+    let mut wrt = csv::WriterBuilder::new()
+        .delimiter(delimiter)
+        .from_writer(io::stdout());
+
+    for r in records {
+        wrt.serialize(r)?;
+    }
+    wrt.flush()?;
+    Ok(())
+}
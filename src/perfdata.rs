@@ -0,0 +1,170 @@
+use std::error::Error;
+use std::str::FromStr;
+
+// Parse and validate performance data in the format of the Monitoring Plugins Development
+// Guidelines:
+//   'label'=value[UOM];[warn];[crit];[min];[max]
+// Multiple values are separated by white space, labels containing white space must be quoted
+// with single quotes. Returns the single values.
+pub fn parse_perfdata(s: &str) -> Result<Vec<String>, Box<dyn Error>> {
+    let mut result = Vec::new();
+
+    for item in split(s)? {
+        validate(&item)?;
+        result.push(item);
+    }
+
+    Ok(result)
+}
+
+// Split performance data at white space outside of quoted labels
+fn split(s: &str) -> Result<Vec<String>, Box<dyn Error>> {
+    let mut items = Vec::new();
+    let mut current = String::new();
+    let mut quoted = false;
+
+    for c in s.chars() {
+        if c == '\'' {
+            // a quote in a quoted label is escaped as '', both states are switched
+            quoted = !quoted;
+        }
+
+        if c.is_whitespace() && !quoted {
+            if !current.is_empty() {
+                items.push(current.clone());
+                current.clear();
+            }
+            continue;
+        }
+
+        current.push(c);
+    }
+
+    if quoted {
+        bail!("Unterminated quote in performance data {}", s);
+    }
+
+    if !current.is_empty() {
+        items.push(current);
+    }
+
+    Ok(items)
+}
+
+fn validate(item: &str) -> Result<(), Box<dyn Error>> {
+    let i = match item.rfind('=') {
+        Some(v) => v,
+        None => bail!("Missing value in performance data {}", item),
+    };
+
+    let label = &item[..i];
+    let data = &item[i + 1..];
+
+    let unquoted = if label.len() >= 2 && label.starts_with('\'') && label.ends_with('\'') {
+        &label[1..label.len() - 1]
+    } else {
+        if label.contains('\'') || label.contains('=') {
+            bail!("Invalid label {} in performance data {}", label, item);
+        }
+        label
+    };
+
+    if unquoted.is_empty() {
+        bail!("Missing label in performance data {}", item);
+    }
+
+    let fields: Vec<&str> = data.split(';').collect();
+    if fields.len() > 5 {
+        bail!("Too many fields in performance data {}", item);
+    }
+
+    // value with optional unit of measurement, U if the value can't be determined
+    let value = fields[0];
+    if value != "U" {
+        let number = value.trim_end_matches(|c: char| c.is_ascii_alphabetic() || c == '%');
+        if f64::from_str(number).is_err() {
+            bail!("Invalid value {} in performance data {}", value, item);
+        }
+    }
+
+    // warning and critical thresholds are ranges, e.g. 10, 10:, ~:10, 10:20 or @10:20
+    for threshold in fields.iter().skip(1).take(2) {
+        if !is_range(threshold) {
+            bail!(
+                "Invalid threshold {} in performance data {}",
+                threshold,
+                item
+            );
+        }
+    }
+
+    for limit in fields.iter().skip(3) {
+        if !limit.is_empty() && f64::from_str(limit).is_err() {
+            bail!(
+                "Invalid minimum or maximum {} in performance data {}",
+                limit,
+                item
+            );
+        }
+    }
+
+    Ok(())
+}
+
+fn is_range(s: &str) -> bool {
+    if s.is_empty() {
+        return true;
+    }
+
+    let s = s.strip_prefix('@').unwrap_or(s);
+    let (start, end) = match s.find(':') {
+        Some(i) => (&s[..i], &s[i + 1..]),
+        None => ("", s),
+    };
+
+    let start_ok = start.is_empty() || start == "~" || f64::from_str(start).is_ok();
+    let end_ok = (end.is_empty() && s.contains(':')) || f64::from_str(end).is_ok();
+
+    start_ok && end_ok
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn valid_perfdata() {
+        assert_eq!(
+            parse_perfdata("time=0.06s;1;2;0 size=1024B").unwrap(),
+            vec!["time=0.06s;1;2;0", "size=1024B"]
+        );
+        assert_eq!(
+            parse_perfdata("  'disk usage /'=80%;@10:20;~:90;0;100   'it''s'=U").unwrap(),
+            vec!["'disk usage /'=80%;@10:20;~:90;0;100", "'it''s'=U"]
+        );
+        assert_eq!(
+            parse_perfdata("load1=0.5;5:;;; rta=-1.5e-3ms").unwrap(),
+            vec!["load1=0.5;5:;;;", "rta=-1.5e-3ms"]
+        );
+        assert!(parse_perfdata("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn invalid_perfdata() {
+        for p in &[
+            "time",
+            "=1",
+            "''=1",
+            "time=",
+            "time=abc",
+            "time=1s2",
+            "time=1;2;3;4;5;6",
+            "time=1;x",
+            "time=1;1;2;min",
+            "'disk usage=1",
+            "disk usage=1",
+        ] {
+            assert!(parse_perfdata(p).is_err(), "{} must be invalid", p);
+        }
+    }
+}
@@ -1,6 +1,10 @@
 use crate::configuration;
 use crate::constants;
 
+use openssl::pkcs12::Pkcs12;
+use openssl::pkey::PKey;
+use openssl::stack::Stack;
+use openssl::x509::X509;
 use reqwest::header;
 use std::error::Error;
 use std::fs;
@@ -60,7 +64,14 @@ pub fn build_client(
             );
         }
         constants::AUTH_CERT => {
-            let raw_cert = fs::read(&cfg.auth_cert)?;
+            let mut raw_cert = fs::read(&cfg.auth_cert)?;
+
+            // If a separate key file is set, certificate and key are PEM encoded
+            if !cfg.auth_cert_key.is_empty() {
+                let raw_key = fs::read(&cfg.auth_cert_key)?;
+                raw_cert = pem_to_pkcs12(&raw_cert, &raw_key, &cfg.auth_cert_password)?;
+            }
+
             let pkcs12 = reqwest::Identity::from_pkcs12_der(&raw_cert, &cfg.auth_cert_password)?;
             bld = bld.identity(pkcs12);
         }
@@ -74,3 +85,34 @@ pub fn build_client(
     let cli = bld.build().unwrap();
     Ok(cli)
 }
+
+// Note: The native-tls backend only accepts client certificates as PKCS#12 bundle, so
+//       PEM encoded certificates and keys (e.g. as found in /var/lib/icinga2/certs) are
+//       converted into a PKCS#12 bundle. Additional certificates in the certificate file
+//       are added as CA chain.
+fn pem_to_pkcs12(cert: &[u8], key: &[u8], password: &str) -> Result<Vec<u8>, Box<dyn Error>> {
+    let certs = X509::stack_from_pem(cert)?;
+    if certs.is_empty() {
+        bail!("No certificate found in client certificate file");
+    }
+
+    let pkey = if password.is_empty() {
+        PKey::private_key_from_pem(key)?
+    } else {
+        PKey::private_key_from_pem_passphrase(key, password.as_bytes())?
+    };
+
+    let mut bld = Pkcs12::builder();
+    bld.name(constants::NAME).pkey(&pkey).cert(&certs[0]);
+
+    if certs.len() > 1 {
+        let mut chain = Stack::new()?;
+        for c in &certs[1..] {
+            chain.push(c.clone())?;
+        }
+        bld.ca(chain);
+    }
+
+    let pkcs12 = bld.build2(password)?;
+    Ok(pkcs12.to_der()?)
+}
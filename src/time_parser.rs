@@ -0,0 +1,266 @@
+use chrono::{DateTime, Duration, Local, NaiveDate, NaiveDateTime, NaiveTime, TimeZone};
+use std::error::Error;
+use std::str::FromStr;
+
+// Parse a point in time and return it as UNIX timestamp. Supported formats are
+//   now
+//   +<duration>, -<duration>                   relative to now, e.g. +2h or -1h30m
+//   today|tomorrow|yesterday [HH:MM[:SS]]      midnight if no time is given
+//   HH:MM[:SS]                                 today
+//   YYYY-MM-DD [HH:MM[:SS]], YYYY-MM-DDTHH:MM[:SS]
+//   RFC3339, e.g. 2020-11-09T23:11:12+02:00
+// Times without time zone are in the local time zone.
+pub fn parse_time(s: &str) -> Result<i64, Box<dyn Error>> {
+    parse_time_at(s, Local::now())
+}
+
+pub fn parse_time_at(s: &str, now: DateTime<Local>) -> Result<i64, Box<dyn Error>> {
+    let s = s.trim();
+    let lower = s.to_lowercase();
+
+    if lower == "now" {
+        return Ok(now.timestamp());
+    }
+
+    if let Some(v) = lower.strip_prefix('+') {
+        return match now.timestamp().checked_add(parse_duration(v)?) {
+            Some(t) => Ok(t),
+            None => bail!("Time {} is out of range", s),
+        };
+    }
+    if let Some(v) = lower.strip_prefix('-') {
+        return match now.timestamp().checked_sub(parse_duration(v)?) {
+            Some(t) => Ok(t),
+            None => bail!("Time {} is out of range", s),
+        };
+    }
+
+    if let Ok(v) = DateTime::parse_from_rfc3339(s) {
+        return Ok(v.timestamp());
+    }
+
+    // date and time are separated by a space or by T (ISO 8601)
+    let (day, time) = match lower.find(' ') {
+        Some(i) => (&lower[..i], lower[i + 1..].trim()),
+        None => match lower.find('t') {
+            Some(i) if lower[..i].contains('-') => (&lower[..i], &lower[i + 1..]),
+            _ => (lower.as_str(), ""),
+        },
+    };
+
+    let today = now.date_naive();
+    let date = match day {
+        "today" => Some(today),
+        "tomorrow" => Some(today + Duration::days(1)),
+        "yesterday" => Some(today - Duration::days(1)),
+        _ => NaiveDate::parse_from_str(day, "%Y-%m-%d").ok(),
+    };
+
+    let (date, time) = match date {
+        Some(d) => {
+            if time.is_empty() {
+                (d, NaiveTime::MIN)
+            } else {
+                (d, parse_time_of_day(time)?)
+            }
+        }
+        None => {
+            // a time without a date is today
+            if !time.is_empty() {
+                bail!("Invalid time {}", s);
+            }
+            (today, parse_time_of_day(day)?)
+        }
+    };
+
+    local_timestamp(&date.and_time(time), s)
+}
+
+fn parse_time_of_day(s: &str) -> Result<NaiveTime, Box<dyn Error>> {
+    if let Ok(v) = NaiveTime::parse_from_str(s, "%H:%M:%S") {
+        return Ok(v);
+    }
+    match NaiveTime::parse_from_str(s, "%H:%M") {
+        Ok(v) => Ok(v),
+        Err(_) => bail!("Invalid time {}", s),
+    }
+}
+
+// UNIX timestamp of a date and time in the local time zone, s is the input used for error messages
+pub fn local_timestamp(t: &NaiveDateTime, s: &str) -> Result<i64, Box<dyn Error>> {
+    match Local.from_local_datetime(t).earliest() {
+        Some(v) => Ok(v.timestamp()),
+        None => bail!("Time {} does not exist in the local time zone", s),
+    }
+}
+
+// Parse a duration and return it in seconds. A duration is a number of seconds or a sequence of
+// numbers with the units w (weeks), d (days), h (hours), m (minutes) and s (seconds),
+// e.g. 90m or 1h30m
+pub fn parse_duration(s: &str) -> Result<i64, Box<dyn Error>> {
+    let s = s.trim();
+
+    if s.is_empty() {
+        bail!("Empty duration");
+    }
+
+    if s.chars().all(|c| c.is_ascii_digit()) {
+        return match i64::from_str(s) {
+            Ok(v) => Ok(v),
+            Err(_) => bail!("Duration {} is too large", s),
+        };
+    }
+
+    let mut result: i64 = 0;
+    let mut number = String::new();
+
+    for c in s.chars() {
+        if c.is_ascii_digit() {
+            number.push(c);
+            continue;
+        }
+
+        let factor = match c.to_ascii_lowercase() {
+            'w' => 7 * 86400,
+            'd' => 86400,
+            'h' => 3600,
+            'm' => 60,
+            's' => 1,
+            _ => bail!("Invalid unit {} in duration {}", c, s),
+        };
+
+        if number.is_empty() {
+            bail!("Missing number before unit {} in duration {}", c, s);
+        }
+
+        let value = match i64::from_str(&number) {
+            Ok(v) => v.checked_mul(factor),
+            Err(_) => None,
+        };
+        result = match value.and_then(|v| result.checked_add(v)) {
+            Some(v) => v,
+            None => bail!("Duration {} is too large", s),
+        };
+        number.clear();
+    }
+
+    if !number.is_empty() {
+        bail!("Missing unit after {} in duration {}", number, s);
+    }
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn now() -> DateTime<Local> {
+        Local.with_ymd_and_hms(2020, 12, 24, 10, 30, 0).unwrap()
+    }
+
+    fn local(y: i32, m: u32, d: u32, h: u32, mi: u32, s: u32) -> i64 {
+        Local
+            .with_ymd_and_hms(y, m, d, h, mi, s)
+            .unwrap()
+            .timestamp()
+    }
+
+    #[test]
+    fn relative_times() {
+        let n = now().timestamp();
+        assert_eq!(parse_time_at("now", now()).unwrap(), n);
+        assert_eq!(parse_time_at("NOW", now()).unwrap(), n);
+        assert_eq!(parse_time_at("+2h", now()).unwrap(), n + 7200);
+        assert_eq!(parse_time_at("-1h30m", now()).unwrap(), n - 5400);
+        assert_eq!(parse_time_at("+90", now()).unwrap(), n + 90);
+    }
+
+    #[test]
+    fn day_names() {
+        assert_eq!(
+            parse_time_at("tomorrow 06:00", now()).unwrap(),
+            local(2020, 12, 25, 6, 0, 0)
+        );
+        assert_eq!(
+            parse_time_at("today", now()).unwrap(),
+            local(2020, 12, 24, 0, 0, 0)
+        );
+        assert_eq!(
+            parse_time_at("yesterday 23:59:30", now()).unwrap(),
+            local(2020, 12, 23, 23, 59, 30)
+        );
+        assert_eq!(
+            parse_time_at("18:00", now()).unwrap(),
+            local(2020, 12, 24, 18, 0, 0)
+        );
+    }
+
+    #[test]
+    fn dates() {
+        assert_eq!(
+            parse_time_at("2020-12-24 18:00", now()).unwrap(),
+            local(2020, 12, 24, 18, 0, 0)
+        );
+        assert_eq!(
+            parse_time_at("2020-12-24T18:00:15", now()).unwrap(),
+            local(2020, 12, 24, 18, 0, 15)
+        );
+        assert_eq!(
+            parse_time_at("2021-01-01", now()).unwrap(),
+            local(2021, 1, 1, 0, 0, 0)
+        );
+    }
+
+    #[test]
+    fn rfc3339() {
+        assert_eq!(
+            parse_time_at("2010-11-09T12:54:11Z", now()).unwrap(),
+            1289307251
+        );
+        assert_eq!(
+            parse_time_at("2020-11-09T23:11:12+02:00", now()).unwrap(),
+            1604956272
+        );
+    }
+
+    #[test]
+    fn invalid_times() {
+        for t in &[
+            "",
+            "later",
+            "tomorrow 25:00",
+            "2020-13-01",
+            "2020-12-24 18",
+            "+",
+            "+2x",
+            "next week",
+        ] {
+            assert!(parse_time_at(t, now()).is_err(), "{} must be invalid", t);
+        }
+    }
+
+    #[test]
+    fn durations() {
+        assert_eq!(parse_duration("3600").unwrap(), 3600);
+        assert_eq!(parse_duration("90m").unwrap(), 5400);
+        assert_eq!(parse_duration("1h30m").unwrap(), 5400);
+        assert_eq!(parse_duration("1d2h3m4s").unwrap(), 93784);
+        assert_eq!(parse_duration("2W").unwrap(), 1209600);
+
+        for d in &["", "h", "1h30", "1y", "1.5h", "-1h"] {
+            assert!(parse_duration(d).is_err(), "{} must be invalid", d);
+        }
+
+        for d in &[
+            "99999999999999999w",
+            "99999999999999999999",
+            "99999999999999999999s",
+            "9223372036854775807s1s",
+        ] {
+            assert!(parse_duration(d).is_err(), "{} must be too large", d);
+        }
+        assert!(parse_time_at("+99999999999999999s", now()).is_ok());
+        assert!(parse_time_at("+9223372036854775807", now()).is_err());
+    }
+}
@@ -14,6 +14,14 @@ pub fn show() {
         -S <service_object>         Schedule downtime for service <service_object>
         --service=<service_object>
 
+        --filter=<expr>             Additional Icinga2 filter expression <expr>, e.g. 'host.vars.os == \"Linux\"'
+                                    or '\"linux\" in host.groups'. The expression is combined with the other
+                                    options. Without a service, host objects are selected.
+
+        --hostgroup=<group>         Select hosts in host group <group>. <group> can be a glob pattern.
+
+        --servicegroup=<group>      Select services in service group <group>. <group> can be a glob pattern.
+
         -a <author>                 Set author of downtime
         --author=<author>           Default: current user
 
@@ -25,15 +33,20 @@ pub fn show() {
                                         no-trigger - Add non-triggered child downtimes
                                     See downtime documentation for futher information.
 
-        -d <sec>                    Set downtime duration for flexible downtime
-        --duration=<sec>            This option is mandatory for flexible downtimes.
+        -d <duration>               Set downtime duration for flexible downtime
+        --duration=<duration>       This option is mandatory for flexible downtimes and
+                                    for downtimes without end time.
+                                    <duration> is a number of seconds or a combination of numbers
+                                    with the units w, d, h, m and s, e.g. 90m or 1h30m.
                                     See downtime documentation for further information.
 
         -e <time>                   Set end time of downtime.
-        --end=<time>                <time> must be in the format as specified in RFC3339,
-                                    e.g. 2010-11-09T12:54:11Z for UTC or 2020-11-09T23:11:12+02:00 for
-                                    a time with time zone.
-                                    This option is mandatory.
+        --end=<time>                <time> can be now, +<duration> or -<duration> relative to now,
+                                    [today|tomorrow|yesterday] HH:MM[:SS] or YYYY-MM-DD [HH:MM[:SS]]
+                                    in the local time zone or a time in RFC3339 format,
+                                    e.g. tomorrow 06:00, +2h or 2020-11-09T23:11:12+02:00.
+                                    Default: start time + duration
+                                    The end time must be after the start time and in the future.
 
         -f                          Add fixed downtime instead of a flexible downtime.
         --fixed                     See downtime documentation for further information.
@@ -42,10 +55,11 @@ pub fn show() {
         --help
 
         -s <time>                   Set start time of downtime
-        --start=<time>              <time> must be in the format as specified in RFC3339,
-                                    e.g. 2010-11-09T12:54:11Z for UTC or 2020-11-09T23:11:12+02:00 for
-                                    a time with time zone.
-                                    This option is mandatory.
+        --start=<time>              <time> can be now, +<duration> or -<duration> relative to now,
+                                    [today|tomorrow|yesterday] HH:MM[:SS] or YYYY-MM-DD [HH:MM[:SS]]
+                                    in the local time zone or a time in RFC3339 format,
+                                    e.g. tomorrow 06:00, +2h or 2020-11-09T23:11:12+02:00.
+                                    Default: now
 
         -t <trigger>                Add trigger for triggered downtime.
         --trigger=<trigger>         See downtime documentation for futher information.
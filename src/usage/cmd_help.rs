@@ -16,6 +16,10 @@ pub fn show(cmd: &str) -> Result<(), Box<dyn Error>> {
             usage::version::show();
             usage::add_downtime::show();
         }
+        "config" => {
+            usage::version::show();
+            usage::config::show();
+        }
         "del-ack" => {
             usage::version::show();
             usage::del_ack::show();
@@ -28,14 +32,62 @@ pub fn show(cmd: &str) -> Result<(), Box<dyn Error>> {
             usage::version::show();
             usage::del_downtime::show();
         }
+        "delay-notification" => {
+            usage::version::show();
+            usage::delay_notification::show();
+        }
+        "disable-notifications" => {
+            usage::version::show();
+            usage::disable_notifications::show();
+        }
+        "enable-notifications" => {
+            usage::version::show();
+            usage::enable_notifications::show();
+        }
         "generate-ticket" => {
             usage::version::show();
             usage::generate_ticket::show();
         }
+        "list-acks" => {
+            usage::version::show();
+            usage::list_acks::show();
+        }
+        "list-comments" => {
+            usage::version::show();
+            usage::list_comments::show();
+        }
+        "list-downtimes" => {
+            usage::version::show();
+            usage::list_downtimes::show();
+        }
+        "list-groups" => {
+            usage::version::show();
+            usage::list_groups::show();
+        }
+        "modify-downtime" => {
+            usage::version::show();
+            usage::modify_downtime::show();
+        }
+        "process-check-result" => {
+            usage::version::show();
+            usage::process_check_result::show();
+        }
         "reschedule-check" => {
             usage::version::show();
             usage::reschedule_check::show();
         }
+        "schedule-downtime" => {
+            usage::version::show();
+            usage::schedule_downtime::show();
+        }
+        "send-custom-notification" => {
+            usage::version::show();
+            usage::send_custom_notification::show();
+        }
+        "set-attr" => {
+            usage::version::show();
+            usage::set_attr::show();
+        }
         "status" => {
             usage::version::show();
             usage::status::show();
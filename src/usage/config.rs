@@ -0,0 +1,11 @@
+pub fn show() {
+    println!(
+        "    config - show configuration
+
+        list                        List Icinga2 instances defined in the configuration file
+
+        -h                          Show this text
+        --help
+"
+    );
+}
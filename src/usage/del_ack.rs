@@ -8,6 +8,14 @@ pub fn show() {
         -S <service_object>         Delete acknowledgement for service <service_object>
         --service=<service_object>
 
+        --filter=<expr>             Additional Icinga2 filter expression <expr>, e.g. 'host.vars.os == \"Linux\"'
+                                    or '\"linux\" in host.groups'. The expression is combined with the other
+                                    options. Without a service, host objects are selected.
+
+        --hostgroup=<group>         Select hosts in host group <group>. <group> can be a glob pattern.
+
+        --servicegroup=<group>      Select services in service group <group>. <group> can be a glob pattern.
+
         -c                          Delete acknowledgement for host/services with CRITICAL state
         --critical
 
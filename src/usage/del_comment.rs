@@ -5,7 +5,7 @@ pub fn show() {
         -A <author>                 Set comment removal author to <author>
         --author=<author>
 
-        -C <comment>                Name of comment to be removed (as it is returned by add-comment).
+        -C <comment>                Name of comment to be removed (as it is returned by add-comment or list-comments).
         --comment=<comment>         Comment name and host/service objects are mutually exclusive
 
         -H <host_object>            Limit comment removal to host <host_object>
@@ -14,6 +14,14 @@ pub fn show() {
         -S <service_object>         Limit comment removal to  service <service_object>
         --service=<service_object>  Comment name and host/service objects are mutually exclusive
 
+        --filter=<expr>             Additional Icinga2 filter expression <expr>, e.g. 'host.vars.os == \"Linux\"'
+                                    or '\"linux\" in host.groups'. The expression is combined with the other
+                                    options. Without a service, host objects are selected.
+
+        --hostgroup=<group>         Select hosts in host group <group>. <group> can be a glob pattern.
+
+        --servicegroup=<group>      Select services in service group <group>. <group> can be a glob pattern.
+
         -h                          Show this text
         --help
 "
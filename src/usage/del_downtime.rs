@@ -4,7 +4,7 @@ pub fn show() {
         -A <author>                 Set downtime removal author to <author>
         --author=<author>
 
-        -D <downtime>               Name of downtime to be removed (as it is returned by add-downtime).
+        -D <downtime>               Name of downtime to be removed (as it is returned by add-downtime or list-downtimes).
         --downtime=<downtime>       Downtime name and host/service objects are mutually exclusive
 
         -H <host_object>            Limit downtime removal to host <host_object>
@@ -13,8 +13,40 @@ pub fn show() {
         -S <service_object>         Limit downtime removal to service <service_object>
         --service=<service_object>  Downtime name and host/service objects are mutually exclusive
 
+        --filter=<expr>             Additional Icinga2 filter expression <expr>, e.g. 'host.vars.os == \"Linux\"'
+                                    or '\"linux\" in host.groups'. The expression is combined with the other
+                                    options. Without a service, host objects are selected.
+
+        --hostgroup=<group>         Select hosts in host group <group>. <group> can be a glob pattern.
+
+        --servicegroup=<group>      Select services in service group <group>. <group> can be a glob pattern.
+
         -h                          Show this text
         --help
+
+        Downtimes can be selected by their attributes. The matching downtimes are listed and removed,
+        a summary of the removed downtimes is shown. Host/service objects can be combined with the
+        following options, the downtime name can't.
+
+        --created-by=<author>       Remove downtimes created by <author>. <author> can be a glob pattern.
+
+        --mine                      Remove downtimes created by the current user
+
+        --comment=<regex>           Remove downtimes with a comment matching the regular expression <regex>
+
+        --created-after=<time>      Remove downtimes created at or after <time>
+        --created-before=<time>     Remove downtimes created before <time>
+
+        --expires-after=<time>      Remove downtimes ending after <time>
+        --expires-before=<time>     Remove downtimes ending before <time>
+
+                                    <time> can be now, +<duration> or -<duration> relative to now,
+                                    [today|tomorrow|yesterday] HH:MM[:SS] or YYYY-MM-DD [HH:MM[:SS]]
+                                    in the local time zone or a time in RFC3339 format,
+                                    e.g. tomorrow 06:00, +2h or 2020-11-09T23:11:12+02:00.
+
+        Example: Remove all downtimes created by the current user today
+            del-downtime --mine --created-after=today
 "
     );
 }
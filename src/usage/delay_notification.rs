@@ -0,0 +1,38 @@
+pub fn show() {
+    println!(
+        "    delay-notification - delay notifications
+
+        -H <host_object>            Delay notifications for host <host_object>
+        --host=<host_object>
+
+        -S <service_object>         Delay notifications for service <service_object>
+        --service=<service_object>
+
+        --filter=<expr>             Additional Icinga2 filter expression <expr>, e.g. 'host.vars.os == \"Linux\"'
+                                    or '\"linux\" in host.groups'. The expression is combined with the other
+                                    options. Without a service, host objects are selected.
+
+        --hostgroup=<group>         Select hosts in host group <group>. <group> can be a glob pattern.
+
+        --servicegroup=<group>      Select services in service group <group>. <group> can be a glob pattern.
+
+        -c                          Delay notifications for host/services with CRITICAL state
+        --critical
+
+        -h                          Show this text
+        --help
+
+        -t <time>                   Delay notifications until <time>. This option is mandatory.
+        --until=<time>              <time> can be now, +<duration> or -<duration> relative to now,
+                                    [today|tomorrow|yesterday] HH:MM[:SS] or YYYY-MM-DD [HH:MM[:SS]]
+                                    in the local time zone or a time in RFC3339 format,
+                                    e.g. +2h or 2020-11-09T23:11:12+02:00.
+
+        -u                          Delay notifications for host/services with UNKNOWN state
+        --unknown
+
+        -w                          Delay notifications for host/services with WARNING state
+        --warning
+"
+    );
+}
@@ -0,0 +1,34 @@
+pub fn show() {
+    println!(
+        "    disable-notifications - disable notifications
+
+        The enable_notifications attribute of the objects is set to false.
+
+        -H <host_object>            Disable notifications for host <host_object>
+        --host=<host_object>
+
+        -S <service_object>         Disable notifications for service <service_object>
+        --service=<service_object>
+
+        --filter=<expr>             Additional Icinga2 filter expression <expr>, e.g. 'host.vars.os == \"Linux\"'
+                                    or '\"linux\" in host.groups'. The expression is combined with the other
+                                    options. Without a service, host objects are selected.
+
+        --hostgroup=<group>         Select hosts in host group <group>. <group> can be a glob pattern.
+
+        --servicegroup=<group>      Select services in service group <group>. <group> can be a glob pattern.
+
+        -c                          Disable notifications for host/services with CRITICAL state
+        --critical
+
+        -h                          Show this text
+        --help
+
+        -u                          Disable notifications for host/services with UNKNOWN state
+        --unknown
+
+        -w                          Disable notifications for host/services with WARNING state
+        --warning
+"
+    );
+}
@@ -0,0 +1,34 @@
+pub fn show() {
+    println!(
+        "    enable-notifications - enable notifications
+
+        The enable_notifications attribute of the objects is set to true.
+
+        -H <host_object>            Enable notifications for host <host_object>
+        --host=<host_object>
+
+        -S <service_object>         Enable notifications for service <service_object>
+        --service=<service_object>
+
+        --filter=<expr>             Additional Icinga2 filter expression <expr>, e.g. 'host.vars.os == \"Linux\"'
+                                    or '\"linux\" in host.groups'. The expression is combined with the other
+                                    options. Without a service, host objects are selected.
+
+        --hostgroup=<group>         Select hosts in host group <group>. <group> can be a glob pattern.
+
+        --servicegroup=<group>      Select services in service group <group>. <group> can be a glob pattern.
+
+        -c                          Enable notifications for host/services with CRITICAL state
+        --critical
+
+        -h                          Show this text
+        --help
+
+        -u                          Enable notifications for host/services with UNKNOWN state
+        --unknown
+
+        -w                          Enable notifications for host/services with WARNING state
+        --warning
+"
+    );
+}
@@ -0,0 +1,22 @@
+pub fn show() {
+    println!(
+        "    list-acks - list acknowledgements
+
+        -H <host_object>            Only list acknowledgements of host <host_object>
+        --host=<host_object>        Without a service filter, only host acknowledgements are listed
+
+        -S <service_object>         Only list acknowledgements of service <service_object>
+        --service=<service_object>
+
+        --filter=<expr>             Additional Icinga2 filter expression <expr> on comment objects,
+                                    e.g. 'comment.entry_time > 1600000000' or 'host.vars.os == \"Linux\"'.
+                                    The expression is combined with the other options.
+
+        -a <author>                 Only list acknowledgements created by <author>
+        --author=<author>
+
+        -h                          Show this text
+        --help
+"
+    );
+}
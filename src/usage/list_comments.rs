@@ -0,0 +1,29 @@
+pub fn show() {
+    println!(
+        "    list-comments - list comments
+
+        -H <host_object>            Only list comments of host <host_object>
+        --host=<host_object>        Without a service filter, only host comments are listed
+
+        -S <service_object>         Only list comments of service <service_object>
+        --service=<service_object>
+
+        --filter=<expr>             Additional Icinga2 filter expression <expr> on comment objects,
+                                    e.g. 'comment.entry_time > 1600000000' or 'host.vars.os == \"Linux\"'.
+                                    The expression is combined with the other options.
+
+        -a <author>                 Only list comments created by <author>
+        --author=<author>
+
+        -h                          Show this text
+        --help
+
+        -t <type>                   Only list comments of type <type>
+        --type=<type>               <type> can be one of:
+                                        user     - comments added by users
+                                        downtime - comments added for downtimes
+                                        flapping - comments added for flapping objects
+                                        ack      - comments added for acknowledgements
+"
+    );
+}
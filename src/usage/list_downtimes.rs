@@ -0,0 +1,43 @@
+pub fn show() {
+    println!(
+        "    list-downtimes - list downtimes
+
+        -A                          Only list downtimes currently in effect
+        --active
+
+        -F                          Only list flexible downtimes
+        --flexible
+
+        -H <host_object>            Only list downtimes of host <host_object>
+        --host=<host_object>        Without a service filter, only host downtimes are listed
+
+        -S <service_object>         Only list downtimes of service <service_object>
+        --service=<service_object>
+
+        --filter=<expr>             Additional Icinga2 filter expression <expr> on downtime objects,
+                                    e.g. 'downtime.entry_time > 1600000000' or 'host.vars.os == \"Linux\"'.
+                                    The expression is combined with the other options.
+
+        -a <author>                 Only list downtimes created by <author>
+        --author=<author>
+
+        -e <time>                   Only list downtimes starting before <time>
+        --end=<time>                <time> can be now, +<duration> or -<duration> relative to now,
+                                    [today|tomorrow|yesterday] HH:MM[:SS] or YYYY-MM-DD [HH:MM[:SS]]
+                                    in the local time zone or a time in RFC3339 format,
+                                    e.g. tomorrow 06:00, +2h or 2020-11-09T23:11:12+02:00.
+
+        -f                          Only list fixed downtimes
+        --fixed
+
+        -h                          Show this text
+        --help
+
+        -s <time>                   Only list downtimes ending after <time>
+        --start=<time>              <time> can be now, +<duration> or -<duration> relative to now,
+                                    [today|tomorrow|yesterday] HH:MM[:SS] or YYYY-MM-DD [HH:MM[:SS]]
+                                    in the local time zone or a time in RFC3339 format,
+                                    e.g. tomorrow 06:00, +2h or 2020-11-09T23:11:12+02:00.
+"
+    );
+}
@@ -0,0 +1,19 @@
+pub fn show() {
+    println!(
+        "    list-groups - list host and service groups with the number of members, the number of
+                  members in a non-OK state and the worst state of the members.
+                  Host members in DOWN state are reported as CRITICAL.
+
+        -g <group>                  Only list groups matching <group>
+        --group=<group>             <group> can be a glob pattern.
+
+        -h                          Show this text
+        --help
+
+        -t <type>                   Only list groups of type <type>
+        --type=<type>               <type> can be one of:
+                                        host    - host groups
+                                        service - service groups
+"
+    );
+}
@@ -2,11 +2,24 @@ pub mod add_ack;
 pub mod add_comment;
 pub mod add_downtime;
 pub mod cmd_help;
+pub mod config;
 pub mod del_ack;
 pub mod del_comment;
 pub mod del_downtime;
+pub mod delay_notification;
+pub mod disable_notifications;
+pub mod enable_notifications;
 pub mod generate_ticket;
+pub mod list_acks;
+pub mod list_comments;
+pub mod list_downtimes;
+pub mod list_groups;
+pub mod modify_downtime;
+pub mod process_check_result;
 pub mod reschedule_check;
+pub mod schedule_downtime;
+pub mod send_custom_notification;
+pub mod set_attr;
 pub mod status;
 pub mod version;
 
@@ -16,20 +29,83 @@ use crate::usage;
 pub fn show() {
     usage::version::show();
     println!(
-        "Usage: {} -c <cfg>|--config=<cfg> [-d|--debug] [-h|--help] [-v|--version] <command> [<command_options>...]
+        "Usage: {} -c <cfg>|--config=<cfg> [-d|--debug] [--dry-run] [--force-many] [-h|--help] [-i <instance>|--instance=<instance>|--all-instances] [--output=<format>] [-v|--version] [--<key>=<value>...] <command> [<command_options>...]
 
     -c <cfg>        Read configuration from <cfg>
-    --config <cfg>  This parameter is mandatory
+    --config <cfg>  Default: $XDG_CONFIG_HOME/icinga2ctl/config.ini or $HOME/.config/icinga2ctl/config.ini
+                    The configuration file is optional if the configuration is
+                    provided completely by command line options and environment variables
 
     -d              Enable debug output
     --debug
 
+    --dry-run       Don't run actions. Show the payload of the action and the host/service objects
+                    which would be affected instead.
+
+    --force-many    Run actions without asking for confirmation, even if more objects than
+                    configured by the keys confirm_threshold and max_objects are affected.
+                    If the number of affected objects exceeds confirm_threshold, the action must
+                    be confirmed, without a terminal the action fails. Actions affecting more than
+                    max_objects objects are refused.
+                    A value of 0 (default) disables the confirmation or the limit.
+
     -h              Shows this text
     --help
 
+    --all-instances Run command on all Icinga2 instances defined in the configuration file
+
+    -i <instance>   Use Icinga2 instance <instance> defined as section [icinga2:<instance>]
+    --instance=<instance>
+                    in the configuration file. The section [icinga2] defines the
+                    instance named default.
+                    <instance> can be a comma separated list of instances. If multiple instances
+                    are used, the command will be run on all instances concurrently and
+                    the results are merged and prefixed by the name of the instance.
+                    If not set, the instance from the environment variable ICINGA2CTL_INSTANCE
+                    or the default instance set by the key default in the section [icinga2ctl]
+                    will be used.
+
+    --output=<format>
+                    Output format for commands returning objects.
+                    <format> can be one of:
+                        text - plain text (default)
+                        json - JSON
+                        yaml - YAML
+                        csv  - comma separated values
+                        tsv  - tab separated values
+
     -v              Show version information
     --version
 
+    --<key>=<value> Override the configuration key <key> with <value>.
+                    The value can also be set by the environment variable ICINGA2CTL_<KEY>,
+                    e.g. --password-file=<file> or ICINGA2CTL_PASSWORD_FILE=<file> for the
+                    key password_file. Command line options take precedence over environment
+                    variables, environment variables take precedence over the configuration file.
+                    Overrides are applied to all selected instances. The URL, the CA file
+                    and the credentials can only be overridden for a single instance.
+                    Supported keys:
+                        --url                   ICINGA2CTL_URL
+                        --auth                  ICINGA2CTL_AUTH
+                        --user                  ICINGA2CTL_USER
+                        --password              ICINGA2CTL_PASSWORD
+                        --password-file         ICINGA2CTL_PASSWORD_FILE
+                        --password-command      ICINGA2CTL_PASSWORD_COMMAND
+                        --password-env          ICINGA2CTL_PASSWORD_ENV
+                        --ca-file               ICINGA2CTL_CA_FILE
+                        --insecure-ssl          ICINGA2CTL_INSECURE_SSL
+                        --auth-cert             ICINGA2CTL_AUTH_CERT
+                        --auth-cert-key         ICINGA2CTL_AUTH_CERT_KEY
+                        --auth-cert-password    ICINGA2CTL_AUTH_CERT_PASSWORD
+                        --auth-cert-password-file
+                                                ICINGA2CTL_AUTH_CERT_PASSWORD_FILE
+                        --auth-cert-password-command
+                                                ICINGA2CTL_AUTH_CERT_PASSWORD_COMMAND
+                        --auth-cert-password-env
+                                                ICINGA2CTL_AUTH_CERT_PASSWORD_ENV
+                        --confirm-threshold     ICINGA2CTL_CONFIRM_THRESHOLD
+                        --max-objects           ICINGA2CTL_MAX_OBJECTS
+
   Commands:
 ",
         constants::NAME
@@ -38,10 +114,23 @@ pub fn show() {
     usage::add_ack::show();
     usage::add_comment::show();
     usage::add_downtime::show();
+    usage::config::show();
     usage::del_ack::show();
     usage::del_comment::show();
     usage::del_downtime::show();
+    usage::delay_notification::show();
+    usage::disable_notifications::show();
+    usage::enable_notifications::show();
     usage::generate_ticket::show();
+    usage::list_acks::show();
+    usage::list_comments::show();
+    usage::list_downtimes::show();
+    usage::list_groups::show();
+    usage::modify_downtime::show();
+    usage::process_check_result::show();
     usage::reschedule_check::show();
+    usage::schedule_downtime::show();
+    usage::send_custom_notification::show();
+    usage::set_attr::show();
     usage::status::show();
 }
@@ -0,0 +1,51 @@
+pub fn show() {
+    println!(
+        "    modify-downtime - change the end time or the duration of downtimes
+
+        A new downtime with the same object, comment, start time and trigger as the old downtime
+        is scheduled first. The old downtime is removed only if the new downtime was scheduled,
+        so there is no gap in the downtime. The names of the old and the new downtime are shown.
+        The new downtime doesn't schedule child downtimes. Because removing a downtime removes its
+        child downtimes too, downtimes with child downtimes (scheduled with --child=trigger or
+        --child=no-trigger) can't be modified and must be removed and scheduled again. The same
+        applies to downtimes triggering other downtimes.
+
+        -D <downtime>               Name of downtime to be modified (as it is returned by add-downtime or list-downtimes).
+        --downtime=<downtime>       Downtime name and host/service objects are mutually exclusive
+
+        -H <host_object>            Modify downtimes of host <host_object>
+        --host=<host_object>        Downtime name and host/service objects are mutually exclusive
+
+        -S <service_object>         Modify downtimes of service <service_object>
+        --service=<service_object>  Downtime name and host/service objects are mutually exclusive
+
+        --filter=<expr>             Additional Icinga2 filter expression <expr>, e.g. 'host.vars.os == \"Linux\"'
+                                    or '\"linux\" in host.groups'. The expression is combined with the other
+                                    options. Without a service, host downtimes are selected.
+
+        --hostgroup=<group>         Select hosts in host group <group>. <group> can be a glob pattern.
+
+        --servicegroup=<group>      Select services in service group <group>. <group> can be a glob pattern.
+
+        -a <author>                 Set author of the modified downtime
+        --author=<author>           Default: author of the old downtime
+
+        -d <duration>               Set new duration of the downtime. For fixed downtimes, the downtime ends
+        --duration=<duration>       <duration> after the start of the downtime.
+                                    <duration> is a number of seconds or a combination of numbers
+                                    with the units w, d, h, m and s, e.g. 90m or 1h30m.
+
+        -e <time>                   Set new end time of the downtime.
+        --end=<time>                <time> can be now, +<duration> or -<duration> relative to now,
+                                    [today|tomorrow|yesterday] HH:MM[:SS] or YYYY-MM-DD [HH:MM[:SS]]
+                                    in the local time zone or a time in RFC3339 format,
+                                    e.g. tomorrow 06:00, +2h or 2020-11-09T23:11:12+02:00.
+
+        -x <duration>               Extend the downtime by <duration>, e.g. 30m
+        --extend=<duration>         End time and extension are mutually exclusive.
+
+        -h                          Show this text
+        --help
+"
+    );
+}
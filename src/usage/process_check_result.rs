@@ -0,0 +1,58 @@
+pub fn show() {
+    println!(
+        "    process-check-result - submit a passive check result
+
+        -H <host_object>            Submit check result for host <host_object>.
+        --host=<host_object>        This option is mandatory unless --from-file is used. Host and service are
+                                    selected by their exact name.
+
+        -S <service_object>         Submit check result for service <service_object> of the host
+        --service=<service_object>  instead of the host.
+
+        -c <source>                 Set source of the check result
+        --check-source=<source>     Default: set by Icinga2
+
+        -f <file>                   Read check results from <file>, - reads from standard input.
+        --from-file=<file>          Every line of <file> is a check result in JSON format, e.g.
+                                    {{\"host\": \"web1\", \"service\": \"http\", \"exit_status\": 2, \"output\": \"CRITICAL\",
+                                     \"perfdata\": \"time=10s;1;5\"}}
+                                    service, perfdata (a string or a list of strings), check_source, ttl,
+                                    execution_start and execution_end (seconds since epoch) are optional.
+                                    Empty lines and lines starting with # are skipped.
+                                    Check results differing only in the host are submitted in a single request
+                                    and the result is reported for every line.
+                                    confirm_threshold and max_objects apply to the number of objects in <file>.
+                                    --from-file can't be combined with the options for a single check result.
+
+        -h                          Show this text
+        --help
+
+        -o <output>                 Plugin output. This option is mandatory.
+        --output=<output>           Like the output of a plugin, performance data can follow a | in
+                                    the first line of the output.
+
+        -p <perfdata>               Performance data in the format 'label'=value[UOM];[warn];[crit];[min];[max].
+        --perfdata=<perfdata>       Multiple values are separated by white space and the option can be repeated.
+                                    The performance data is validated before it is submitted.
+
+        -P <n>                      Number of concurrent requests for --from-file.
+        --parallel=<n>              Default: 4
+
+        -s <status>                 Exit status of the check. This option is mandatory.
+        --status=<status>           <status> can be one of:
+                                        0, ok, 1, warning, 2, critical, 3, unknown for services
+                                        0, up, 1, down for hosts
+
+        -t <ttl>                    Time to live of the check result. If no new check result is received
+        --ttl=<ttl>                 within <ttl>, the check is executed. <ttl> is a number of seconds or a
+                                    combination of numbers with the units w, d, h, m and s, e.g. 5m.
+
+        --execution-end=<time>      Start and end time of the check execution.
+        --execution-start=<time>    <time> can be now, +<duration> or -<duration> relative to now,
+                                    [today|tomorrow|yesterday] HH:MM[:SS] or YYYY-MM-DD [HH:MM[:SS]]
+                                    in the local time zone or a time in RFC3339 format,
+                                    e.g. -5m or 2020-11-09T23:11:12+02:00.
+                                    Default: time of submission
+"
+    );
+}
@@ -14,11 +14,20 @@ pub fn show() {
 
         -S <service_object>         Reschedule checks for service <service_object>
         --service=<service_object>
+
+        --filter=<expr>             Additional Icinga2 filter expression <expr>, e.g. 'host.vars.os == \"Linux\"'
+                                    or '\"linux\" in host.groups'. The expression is combined with the other
+                                    options. Without a service, host objects are selected.
+
+        --hostgroup=<group>         Select hosts in host group <group>. <group> can be a glob pattern.
+
+        --servicegroup=<group>      Select services in service group <group>. <group> can be a glob pattern.
  
         -a <time>                   Instead of rerun check immediately, run check at <time>
-        --at <time>                 <time> must be in the format as specified in RFC3339,
-                                    e.g. 2010-11-09T12:54:11Z for UTC or 2020-11-09T23:11:12+02:00 for
-                                    a time with time zone.
+        --at <time>                 <time> can be now, +<duration> or -<duration> relative to now,
+                                    [today|tomorrow|yesterday] HH:MM[:SS] or YYYY-MM-DD [HH:MM[:SS]]
+                                    in the local time zone or a time in RFC3339 format,
+                                    e.g. tomorrow 06:00, +2h or 2020-11-09T23:11:12+02:00.
  
         -c                          Reschedule host/services checks with CRITICAL state
         --critical
@@ -39,5 +48,5 @@ pub fn show() {
         --warning
 
  "
-     );
+    );
 }
@@ -0,0 +1,75 @@
+pub fn show() {
+    println!(
+        "    schedule-downtime - schedule recurring downtimes
+
+        The downtimes for the next occurrences of the time ranges are added, or a ScheduledDowntime
+        object is created in the config package {} (--config) to let Icinga2 schedule the
+        downtimes.
+
+        -C <comment>                Set downtime comment.
+        --comment=<comment>         This option is mandatory.
+
+        -H <host_object>            Schedule downtime for host <host_object>
+        --host=<host_object>
+
+        -S <service_object>         Schedule downtime for service <service_object>
+        --service=<service_object>
+
+        --filter=<expr>             Additional Icinga2 filter expression <expr>, e.g. 'host.vars.os == \"Linux\"'
+                                    or '\"linux\" in host.groups'. The expression is combined with the other
+                                    options. Without a service, host objects are selected.
+                                    For --config the expression is used in the assign rule of
+                                    the ScheduledDowntime object.
+
+        --hostgroup=<group>         Select hosts in host group <group>. <group> can be a glob pattern.
+
+        --servicegroup=<group>      Select services in service group <group>. <group> can be a glob pattern.
+
+        -N <name>                   Name of the ScheduledDowntime object.
+        --name=<name>               This option is mandatory for --config. An existing object with the same
+                                    name is replaced.
+
+        -a <author>                 Set author of downtime
+        --author=<author>           Default: current user
+
+        -c <childopts>              Schedule child downtime.
+        --child=<childopts>         <childopts> can be one of:
+                                        no         - don't schedule child downtimes
+                                                     This is the default behavior.
+                                        trigger    - Add child downtimes triggered by the downtime to be added
+                                        no-trigger - Add non-triggered child downtimes
+                                    See downtime documentation for futher information.
+
+        --config                    Create a ScheduledDowntime object instead of adding downtimes.
+                                    Waits until Icinga2 has validated the new configuration.
+
+        -d <duration>               Set downtime duration for flexible downtime
+        --duration=<duration>       This option is mandatory for flexible downtimes.
+                                    <duration> is a number of seconds or a combination of numbers
+                                    with the units w, d, h, m and s, e.g. 90m or 1h30m.
+
+        -f                          Add fixed downtime instead of a flexible downtime.
+        --fixed                     See downtime documentation for further information.
+
+        -h                          Show this text
+        --help
+
+        -n <count>                  Number of downtimes to add.
+        --count=<count>             Default: 1
+
+        -r <day>=<ranges>           Time ranges of the downtime in the Icinga2 ranges syntax.
+        --range=<day>=<ranges>      This option is mandatory and can be repeated.
+                                    <ranges> is a comma separated list of HH:MM-HH:MM in the local time zone.
+                                    Without --config, <day> can be one of:
+                                        <weekday>          - every week, e.g. monday
+                                        <weekday> <n>      - n-th weekday of the month, e.g. tuesday 2
+                                                             or friday -1 for the last friday
+                                        day <n>            - n-th day of the month, e.g. day 1
+                                                             or day -1 for the last day
+                                        YYYY-MM-DD         - a single day
+                                    With --config all ranges supported by Icinga2 can be used.
+                                    Example: --range='monday=02:00-04:00' --range='day -1=22:00-24:00'
+",
+        crate::constants::NAME
+    );
+}
@@ -0,0 +1,33 @@
+pub fn show() {
+    println!(
+        "    send-custom-notification - send a custom notification
+
+        -C <comment>                Set notification comment.
+        --comment=<comment>         This option is mandatory.
+
+        -H <host_object>            Send notification for host <host_object>
+        --host=<host_object>
+
+        -S <service_object>         Send notification for service <service_object>
+        --service=<service_object>
+
+        --filter=<expr>             Additional Icinga2 filter expression <expr>, e.g. 'host.vars.os == \"Linux\"'
+                                    or '\"linux\" in host.groups'. The expression is combined with the other
+                                    options. Without a service, host objects are selected.
+
+        --hostgroup=<group>         Select hosts in host group <group>. <group> can be a glob pattern.
+
+        --servicegroup=<group>      Select services in service group <group>. <group> can be a glob pattern.
+
+        -a <author>                 Set author of notification
+        --author=<author>           Default: current user
+
+        -f                          Send notification even if the object is in a downtime or the
+        --force                     notification filters of the user don't match
+
+        -h                          Show this text
+        --help
+
+"
+    );
+}
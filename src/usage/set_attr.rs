@@ -0,0 +1,43 @@
+pub fn show() {
+    println!(
+        "    set-attr - enable, disable or revert object attributes
+
+        The attributes are changed at runtime and are kept until they are reverted to the value
+        of the configuration.
+
+        -H <host_object>            Set attributes of host <host_object>
+        --host=<host_object>
+
+        -S <service_object>         Set attributes of service <service_object>
+        --service=<service_object>
+
+        --filter=<expr>             Additional Icinga2 filter expression <expr>, e.g. 'host.vars.os == \"Linux\"'
+                                    or '\"linux\" in host.groups'. The expression is combined with the other
+                                    options. Without a service, host objects are selected.
+
+        --hostgroup=<group>         Select hosts in host group <group>. <group> can be a glob pattern.
+
+        --servicegroup=<group>      Select services in service group <group>. <group> can be a glob pattern.
+
+        -d <attribute>              Disable <attribute>. The option can be repeated.
+        --disable=<attribute>       <attribute> can be one of:
+                                        active-checks  - enable_active_checks
+                                        event-handler  - enable_event_handler
+                                        flapping       - enable_flapping
+                                        notifications  - enable_notifications
+                                        passive-checks - enable_passive_checks
+                                        perfdata       - enable_perfdata
+
+        -e <attribute>              Enable <attribute>. The option can be repeated.
+        --enable=<attribute>        See --disable for the supported attributes.
+
+        -h                          Show this text
+        --help
+
+        -r <attribute>              Revert <attribute> to the value of the configuration. The option can
+        --revert=<attribute>        be repeated, all reverts all supported attributes.
+                                    See --disable for the supported attributes.
+                                    Note: Requires Icinga2 2.13 or newer.
+"
+    );
+}
@@ -2,6 +2,13 @@ pub fn show() {
     println!(
         "   status - show Icinga2 host/service status
 
+        -C                          Summarise status as monitoring plugin output with performance data
+        --check                     and exit with the plugin exit code (0 - OK, 1 - WARNING, 2 - CRITICAL,
+                                    3 - UNKNOWN) of the worst unhandled state. Problems which are
+                                    acknowledged or in a downtime are handled.
+                                    Host objects in DOWN state are counted as CRITICAL.
+                                    If no object matches, UNKNOWN is reported.
+
         -H <host_object>            Show status of host <host_object>
         --host=<host_object>
 
@@ -11,6 +18,14 @@ pub fn show() {
         -S <service_object>         Show status of service <service_object>
         --service=<service_object>
 
+        --filter=<expr>             Additional Icinga2 filter expression <expr>, e.g. 'host.vars.os == \"Linux\"'
+                                    or '\"linux\" in host.groups'. The expression is combined with the other
+                                    options. Without a service, host objects are selected.
+
+        --hostgroup=<group>         Select hosts in host group <group>. <group> can be a glob pattern.
+
+        --servicegroup=<group>      Select services in service group <group>. <group> can be a glob pattern.
+
         -a <type>                   Only show states with <ack> acknowledgement type
         --ack=<type>                    yes    - acknowledged
                                         no     - not acknowledged
@@ -1,4 +1,14 @@
+use crate::configuration;
+use crate::constants;
+
 use ansi_term::Colour::{Green, Purple, Red, Yellow};
+use chrono::{Local, TimeZone};
+use std::error::Error;
+use std::io::{self, BufRead, Write};
+use std::sync::Mutex;
+
+// Commands on multiple instances run concurrently, only one question can be asked at a time
+static PROMPT: Mutex<()> = Mutex::new(());
 
 pub fn state_to_string(s: f64) -> String {
     match s.round() as i64 {
@@ -11,12 +21,75 @@ pub fn state_to_string(s: f64) -> String {
     .to_string()
 }
 
-pub fn print_state(s: &str, st: &str, a: f64, color: bool) {
-    let ack = !matches!(a.round() as i64, 0);
+// Hosts only know the states UP and DOWN
+pub fn host_state_to_string(s: f64) -> String {
+    match s.round() as i64 {
+        0 => "UP",
+        1 => "DOWN",
+        _ => "???",
+    }
+    .to_string()
+}
+
+pub fn state_type_to_string(s: f64) -> String {
+    match s.round() as i64 {
+        0 => "SOFT",
+        1 => "HARD",
+        _ => "???",
+    }
+    .to_string()
+}
+
+pub fn ack_to_string(a: f64) -> String {
+    match a.round() as i8 {
+        constants::ICINGA2_ACK_NONE => "no",
+        constants::ICINGA2_ACK_ACK => "yes",
+        constants::ICINGA2_ACK_STICKY => "sticky",
+        _ => "???",
+    }
+    .to_string()
+}
+
+pub fn comment_type_to_string(t: f64) -> String {
+    match t.round() as i8 {
+        constants::ICINGA2_COMMENT_USER => "user",
+        constants::ICINGA2_COMMENT_DOWNTIME => "downtime",
+        constants::ICINGA2_COMMENT_FLAPPING => "flapping",
+        constants::ICINGA2_COMMENT_ACK => "ack",
+        _ => "???",
+    }
+    .to_string()
+}
+
+// Results are prefixed with the name of the instance if commands are run on multiple instances
+pub fn instance_prefix(cfg: &configuration::Configuration) -> String {
+    if cfg.show_instance {
+        format!("{}: ", cfg.instance)
+    } else {
+        String::new()
+    }
+}
+
+// Ask a yes/no question on the terminal, everything except yes is taken as no
+pub fn confirm(question: &str) -> Result<bool, Box<dyn Error>> {
+    let _lock = match PROMPT.lock() {
+        Ok(v) => v,
+        Err(e) => e.into_inner(),
+    };
 
+    eprint!("{} [y/N] ", question);
+    io::stderr().flush()?;
+
+    let mut answer = String::new();
+    io::stdin().lock().read_line(&mut answer)?;
+
+    Ok(matches!(answer.trim().to_lowercase().as_str(), "y" | "yes"))
+}
+
+pub fn print_state(s: &str, st: &str, ack: bool, color: bool) {
     if color {
         match st {
-            "OK" => {
+            "OK" | "UP" => {
                 println!("{}", Green.paint(s));
             }
             "WARNING" => {
@@ -26,7 +99,7 @@ pub fn print_state(s: &str, st: &str, a: f64, color: bool) {
                     println!("{}", Yellow.bold().underline().paint(s));
                 }
             }
-            "CRITICAL" => {
+            "CRITICAL" | "DOWN" => {
                 if ack {
                     println!("{}", Red.paint(s));
                 } else {
@@ -48,3 +121,74 @@ pub fn print_state(s: &str, st: &str, a: f64, color: bool) {
         println!("{}", s);
     }
 }
+
+pub fn timestamp_to_rfc3339(t: f64) -> String {
+    if t <= 0.0 {
+        return String::new();
+    }
+    match Local.timestamp_opt(t.round() as i64, 0).single() {
+        Some(v) => v.to_rfc3339(),
+        None => String::new(),
+    }
+}
+
+pub fn format_duration(d: i64) -> String {
+    if d <= 0 {
+        return "0s".to_string();
+    }
+
+    let mut remain = d;
+    let mut result = String::new();
+    for (unit, secs) in &[("d", 86400), ("h", 3600), ("m", 60), ("s", 1)] {
+        if remain >= *secs {
+            result.push_str(&format!("{}{}", remain / secs, unit));
+            remain %= secs;
+        }
+    }
+    result
+}
+
+pub fn print_table(header: &[&str], rows: &[Vec<String>]) {
+    let mut width: Vec<usize> = header.iter().map(|h| h.chars().count()).collect();
+
+    for r in rows {
+        for (i, c) in r.iter().enumerate() {
+            if c.chars().count() > width[i] {
+                width[i] = c.chars().count();
+            }
+        }
+    }
+
+    let head: Vec<String> = header.iter().map(|h| h.to_string()).collect();
+    print_table_row(&head, &width);
+    for r in rows {
+        print_table_row(r, &width);
+    }
+}
+
+// The first column of the table must be the instance name, it will be omitted if results
+// are not shown for multiple instances
+pub fn print_instance_table(
+    cfg: &configuration::Configuration,
+    header: &[&str],
+    rows: &[Vec<String>],
+) {
+    if cfg.show_instance {
+        print_table(header, rows);
+    } else {
+        let rows: Vec<Vec<String>> = rows.iter().map(|r| r[1..].to_vec()).collect();
+        print_table(&header[1..], &rows);
+    }
+}
+
+fn print_table_row(row: &[String], width: &[usize]) {
+    let mut line = String::new();
+    for (i, c) in row.iter().enumerate() {
+        if i == row.len() - 1 {
+            line.push_str(c);
+        } else {
+            line.push_str(&format!("{:w$}  ", c, w = width[i]));
+        }
+    }
+    println!("{}", line);
+}